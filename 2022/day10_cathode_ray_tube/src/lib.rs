@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::input::process;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::render::{Frame, Recorder, Render};
+use aoc_common::trace::trace;
+
+enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        if s.starts_with("noop") {
+            return Ok(Instruction::Noop);
+        } else if s.starts_with("addx") {
+            let (_, number) = source.split_once(s, " ")?;
+            return Ok(Instruction::Addx(source.parse(number)?));
+        }
+        Err(source.error(s, "unknown instruction"))
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+struct CRT {
+    wide: usize,
+    screen: Vec<Vec<char>>,
+
+    screen_i_wide: usize,
+    screen_i_high: usize,
+}
+
+impl CRT {
+    fn new(wide: usize, high: usize) -> Self {
+        let mut screen = Vec::new();
+        for i in 0..high {
+            screen.push(Vec::new());
+            for _ in 0..wide {
+                screen.get_mut(i).unwrap().push('.');
+            }
+        }
+        Self {
+            screen,
+            wide,
+            screen_i_high: 0,
+            screen_i_wide: 0,
+        }
+    }
+
+    // Sprite is 3 pixels wide, and the X register sets the horizontal position of the middle of that sprite.
+    pub fn draw_pixel(&mut self, sprite_middle_position: i32) {
+        let mut pixel = '.';
+        if i32::abs(self.screen_i_wide as i32 - sprite_middle_position) < 2 {
+            pixel = '#';
+        }
+        self.screen[self.screen_i_high][self.screen_i_wide] = pixel;
+        self.screen_i_wide += 1;
+        if self.screen_i_wide == self.wide {
+            self.screen_i_wide = 0;
+            self.screen_i_high += 1;
+        }
+    }
+}
+
+impl Render for CRT {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::new(self.wide, self.screen.len());
+        for (y, row) in self.screen.iter().enumerate() {
+            for (x, &pixel) in row.iter().enumerate() {
+                frame.set((x, y), pixel);
+            }
+        }
+        frame
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+struct CPU {
+    cycle: u32,
+    reg_x: i32,
+
+    crt: CRT,
+
+    breakpoints: HashSet<u32>,
+    breakpoint_register: HashMap<u32, i32>,
+}
+
+impl CPU {
+    fn new() -> Self {
+        Self {
+            cycle: 1,
+            reg_x: 1,
+            breakpoints: HashSet::new(),
+            breakpoint_register: HashMap::new(),
+            crt: CRT::new(40, 6),
+        }
+    }
+
+    pub fn set_breakpoints(&mut self, breakpoints: Vec<u32>) {
+        self.breakpoints.clear();
+        for bp in breakpoints {
+            self.breakpoints.insert(bp);
+        }
+    }
+
+    pub fn exec(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::Noop => {
+                self.cycle();
+            }
+            Instruction::Addx(v) => {
+                self.cycle();
+                self.cycle();
+                self.reg_x += v;
+            }
+        }
+    }
+
+    fn cycle(&mut self) {
+        trace!("cycle {}: X = {}", self.cycle, self.reg_x);
+        self.crt.draw_pixel(self.reg_x);
+
+        if self.breakpoints.contains(&self.cycle) {
+            self.breakpoint_register.insert(self.cycle, self.reg_x);
+        }
+
+        self.cycle += 1;
+    }
+
+    #[allow(dead_code)]
+    pub fn signal_strength(cycle: &u32, reg_x: &i32) -> i32 {
+        (*cycle as i32) * reg_x
+    }
+}
+
+fn run_program<R: BufRead>(input: R, recorder: &mut Recorder) -> Result<CPU, Box<dyn Error>> {
+    let mut cpu = CPU::new();
+    cpu.set_breakpoints(vec![20, 60, 100, 140, 180, 220]);
+
+    process(
+        input,
+        |line| Ok(Instruction::from_str(&line)?),
+        |instruction| {
+            cpu.exec(instruction);
+            recorder.record(&cpu.crt)?;
+            Ok(())
+        },
+    )?;
+
+    Ok(cpu)
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i32, Box<dyn Error>> {
+    let cpu = run_program(input, &mut Recorder::none())?;
+
+    let mut signal_strengths_sum = 0;
+    for (cycle, reg_x) in cpu.breakpoint_register.iter() {
+        let signal_strength = CPU::signal_strength(cycle, reg_x);
+        signal_strengths_sum += signal_strength;
+    }
+    Ok(signal_strengths_sum)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<String, Box<dyn Error>> {
+    part2_recorded(input, &mut Recorder::none())
+}
+
+// Like `part2`, recording the screen after every instruction.
+pub fn part2_recorded<R: BufRead>(
+    input: R,
+    recorder: &mut Recorder,
+) -> Result<String, Box<dyn Error>> {
+    let cpu = run_program(input, recorder)?;
+    Ok(cpu.crt.render().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Instruction>(&fuzz::lines(EXAMPLE));
+    }
+}
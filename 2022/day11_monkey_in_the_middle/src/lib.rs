@@ -0,0 +1,297 @@
+use aoc_common::input::records;
+use aoc_common::parse::{ParseError, Source};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug)]
+struct Item {
+    worry_level: i64,
+}
+
+impl Item {
+    fn new(worry_level: i64) -> Self {
+        Self { worry_level }
+    }
+}
+
+impl FromStr for Item {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            worry_level: Source::new(s).parse(s)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Value {
+    Old(),
+    Value(i64),
+}
+
+#[derive(Debug)]
+enum Operation {
+    Add(),
+    Multiply(),
+}
+
+#[derive(Debug)]
+struct MonkeyOperation {
+    v1: Value,
+    op: Operation,
+    v2: Value,
+}
+
+impl MonkeyOperation {
+    fn perform(&self, old: i64) -> i64 {
+        let v1 = match self.v1 {
+            Value::Old() => old,
+            Value::Value(v) => v,
+        };
+        let v2 = match self.v2 {
+            Value::Old() => old,
+            Value::Value(v) => v,
+        };
+        match self.op {
+            Operation::Add() => v1 + v2,
+            Operation::Multiply() => v1 * v2,
+        }
+    }
+}
+
+enum WorryRelief {
+    // monkey gets bored, the worry level is divided by 3 (part1)
+    Bored,
+    // worry level is kept modulo the product of all test divisors to avoid overflow (part2)
+    Modulo(i64),
+}
+
+#[derive(Debug)]
+struct MonkeyTest {
+    divisible_by: i64,
+    if_true_throw_to_monkey: usize,
+    if_false_throw_to_monkey: usize,
+}
+
+impl MonkeyTest {
+    fn perform(&self, worry_level: i64) -> usize {
+        if worry_level % self.divisible_by == 0 {
+            self.if_true_throw_to_monkey
+        } else {
+            self.if_false_throw_to_monkey
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Monkey {
+    items: VecDeque<Item>,
+    operation: MonkeyOperation,
+    test: MonkeyTest,
+
+    items_inspections: u32,
+}
+
+impl Monkey {
+    fn has_items(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    fn throw_item(&mut self, relief: &WorryRelief) -> (usize, Item) {
+        let item = self.items.pop_front().unwrap();
+
+        // perform operation
+        let mut worry_level = item.worry_level;
+        worry_level = self.operation.perform(worry_level);
+        worry_level = match relief {
+            WorryRelief::Bored => worry_level / 3,
+            WorryRelief::Modulo(m) => worry_level % m,
+        };
+
+        // perform test
+        let monkey_receiver = self.test.perform(worry_level);
+
+        self.items_inspections += 1;
+        (monkey_receiver, Item::new(worry_level))
+    }
+
+    fn receive_item(&mut self, item: Item) {
+        self.items.push_back(item)
+    }
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let value = |v: &str| match v {
+            "old" => Ok(Value::Old()),
+            _ => Ok(Value::Value(source.parse(v)?)),
+        };
+
+        let mut items = VecDeque::new();
+        let mut operation: Option<MonkeyOperation> = None;
+        let mut test: MonkeyTest = MonkeyTest {
+            divisible_by: 0,
+            if_true_throw_to_monkey: 0,
+            if_false_throw_to_monkey: 0,
+        };
+
+        for line in s.lines() {
+            if let Some(items_str) = line.strip_prefix("  Starting items: ") {
+                for item in items_str.split(", ") {
+                    items.push_back(source.parse(item)?);
+                }
+            }
+            if let Some(operation_str) = line.strip_prefix("  Operation: new = ") {
+                let ops: Vec<&str> = operation_str.split(" ").collect();
+                if ops.len() != 3 {
+                    return Err(source.error(operation_str, "expected 'a + b' or 'a * b'"));
+                }
+                let op = match ops[1] {
+                    "+" => Operation::Add(),
+                    "*" => Operation::Multiply(),
+                    _ => return Err(source.error(ops[1], "unknown operation")),
+                };
+                operation = Some(MonkeyOperation {
+                    v1: value(ops[0])?,
+                    op,
+                    v2: value(ops[2])?,
+                });
+            }
+            if let Some(divisible_by) = line.strip_prefix("  Test: divisible by ") {
+                test.divisible_by = source.parse(divisible_by)?;
+                if test.divisible_by == 0 {
+                    return Err(source.error(divisible_by, "cannot divide by zero"));
+                }
+            }
+            if let Some(monkey) = line.strip_prefix("    If true: throw to monkey ") {
+                test.if_true_throw_to_monkey = source.parse(monkey)?;
+            }
+            if let Some(monkey) = line.strip_prefix("    If false: throw to monkey ") {
+                test.if_false_throw_to_monkey = source.parse(monkey)?;
+            }
+        }
+        if test.divisible_by == 0 {
+            return Err(source.error(s, "monkey has no test"));
+        }
+        Ok(Self {
+            items,
+            operation: operation.ok_or_else(|| source.error(s, "monkey has no operation"))?,
+            test,
+            items_inspections: 0,
+        })
+    }
+}
+
+struct GameKeepAway {
+    monkeys: Vec<Monkey>,
+}
+
+impl GameKeepAway {
+    fn new() -> Self {
+        Self { monkeys: vec![] }
+    }
+
+    pub fn add_monkey(&mut self, monkey: Monkey) {
+        self.monkeys.push(monkey);
+    }
+
+    pub fn round(&mut self, relief: &WorryRelief) {
+        for i in 0..self.monkeys.len() {
+            let monkey = self.monkeys.get_mut(i).unwrap();
+
+            let mut receivers: Vec<(usize, Item)> = Vec::new();
+            while monkey.has_items() {
+                let (to_monkey, item) = monkey.throw_item(relief);
+                receivers.push((to_monkey, item));
+            }
+
+            for (to_monkey, item) in receivers {
+                let monkey_receiver = self.monkeys.get_mut(to_monkey).unwrap();
+                monkey_receiver.receive_item(item);
+            }
+        }
+    }
+
+    fn test_divisors_product(&self) -> i64 {
+        self.monkeys.iter().map(|m| m.test.divisible_by).product()
+    }
+
+    fn monkey_business(&self) -> u64 {
+        let mut inspections: Vec<u64> = self
+            .monkeys
+            .iter()
+            .map(|m| m.items_inspections as u64)
+            .collect();
+        inspections.sort_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product()
+    }
+}
+
+fn add_monkeys_from_input_file<R: BufRead>(
+    game: &mut GameKeepAway,
+    input: R,
+) -> Result<(), Box<dyn Error>> {
+    for record in records(input) {
+        let record = record?;
+        let monkey: Monkey = record.parse()?;
+        game.add_monkey(monkey);
+    }
+    let monkeys = game.monkeys.len();
+    for (i, monkey) in game.monkeys.iter().enumerate() {
+        let test = &monkey.test;
+        if test
+            .if_true_throw_to_monkey
+            .max(test.if_false_throw_to_monkey)
+            >= monkeys
+        {
+            return Err(format!("monkey {} throws to a monkey that does not exist", i).into());
+        }
+    }
+    Ok(())
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    add_monkeys_from_input_file(&mut GameKeepAway::new(), input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let mut game = GameKeepAway::new();
+    add_monkeys_from_input_file(&mut game, input)?;
+
+    for _ in 0..20 {
+        game.round(&WorryRelief::Bored);
+    }
+    Ok(game.monkey_business())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let mut game = GameKeepAway::new();
+    add_monkeys_from_input_file(&mut game, input)?;
+
+    let relief = WorryRelief::Modulo(game.test_divisors_product());
+    for _ in 0..10_000 {
+        game.round(&relief);
+    }
+    Ok(game.monkey_business())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Monkey>(&fuzz::records(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
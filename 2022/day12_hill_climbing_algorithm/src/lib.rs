@@ -0,0 +1,175 @@
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::random::Rng;
+use aoc_common::search;
+use std::error::Error;
+use std::io::BufRead;
+
+#[derive(Debug)]
+struct Hill {
+    grid: Grid<char>,
+    start: Pos,
+    destination: Pos,
+}
+
+impl Hill {
+    pub fn new(grid: Grid<char>) -> Result<Hill, Box<dyn Error>> {
+        let position = |c| grid.find(&c).ok_or(format!("hill has no '{}'", c));
+        Ok(Hill {
+            start: position('S')?,
+            destination: position('E')?,
+            grid,
+        })
+    }
+
+    pub fn climb(&self) -> Result<u32, Box<dyn Error>> {
+        let explored = search::bfs(
+            [self.start],
+            |&position| {
+                self.possible_steps_from(position)
+                    .into_iter()
+                    .filter(move |&pos| self.can_do_step_up(position, pos))
+            },
+            |&position| position == self.destination,
+        );
+        let steps = explored.goal_cost().ok_or("no path from S to E")?;
+        Ok(steps as u32)
+    }
+
+    pub fn find_hiking_trail(&self) -> Result<u32, Box<dyn Error>> {
+        let explored = search::bfs(
+            [self.destination],
+            |&position| {
+                self.possible_steps_from(position)
+                    .into_iter()
+                    .filter(move |&pos| self.can_do_step_down(position, pos))
+            },
+            |&position| {
+                let c = self.get_position(position);
+                c == 'a' || c == 'S'
+            },
+        );
+        let steps = explored.goal_cost().ok_or("no path from any 'a' to E")?;
+        Ok(steps as u32)
+    }
+
+    fn can_do_step_up(&self, a: Pos, b: Pos) -> bool {
+        let mut a_char = self.get_position(a);
+        let mut b_char = self.get_position(b);
+        if a_char == 'S' {
+            a_char = 'a';
+        }
+        if b_char == 'S' {
+            b_char = 'a';
+        }
+        if b_char == 'E' {
+            b_char = 'z';
+        }
+        b_char as i32 <= a_char as i32 + 1
+    }
+
+    fn can_do_step_down(&self, a: Pos, b: Pos) -> bool {
+        let mut a_char = self.get_position(a);
+        let mut b_char = self.get_position(b);
+        if a_char == 'S' {
+            a_char = 'a';
+        }
+        if b_char == 'S' {
+            b_char = 'a';
+        }
+        if a_char == 'E' {
+            a_char = 'z';
+        }
+        if b_char == 'E' {
+            b_char = 'z';
+        }
+        a_char as i32 <= (b_char as i32) + 1
+    }
+
+    fn possible_steps_from(&self, pos: Pos) -> Vec<Pos> {
+        self.grid.neighbours4(pos).collect()
+    }
+
+    fn get_position(&self, pos: Pos) -> char {
+        self.grid[pos]
+    }
+}
+
+fn load_hill<R: BufRead>(input: R) -> Result<Hill, Box<dyn Error>> {
+    Hill::new(Grid::parse_chars(input)?)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_hill(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    load_hill(input)?.climb()
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    load_hill(input)?.find_hiking_trail()
+}
+
+// A hill of `size` by `size` squares (at least 14) of random heights, with
+// `S` and `E` in opposite corners. A path goes from one to the other without
+// ever climbing too steeply, rising from `a` to `z` on the way.
+pub fn generate(seed: u64, size: usize) -> String {
+    let size = size.max(14);
+    let mut rng = Rng::new(seed);
+    let mut rows: Vec<Vec<char>> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+    let length = 2 * size - 1;
+    let (mut x, mut y) = (0, 0);
+    for i in 0..length {
+        rows[y][x] = char::from(b'a' + (25 * i / (length - 1)) as u8);
+        if x == size - 1 || (y < size - 1 && rng.chance(50)) {
+            y += 1;
+        } else {
+            x += 1;
+        }
+    }
+    rows[0][0] = 'S';
+    rows[size - 1][size - 1] = 'E';
+    rows.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for (seed, size) in [(0, 1), (1, 14), (2, 60)] {
+            let input = generate(seed, size);
+            // climbing from a to z takes 25 steps at least
+            let steps = part1(input.as_bytes()).unwrap();
+            assert!((25..=2 * size.max(14) as u32 - 2).contains(&steps));
+            assert!(part2(input.as_bytes()).unwrap() <= steps);
+        }
+    }
+
+    #[test]
+    fn unreachable_summits_are_errors() {
+        let input = "SzE\nabc\n";
+        let err = part1(input.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "no path from S to E");
+        let err = part2(input.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "no path from any 'a' to E");
+    }
+}
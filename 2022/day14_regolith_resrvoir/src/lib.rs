@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::geometry::Point2;
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::render::{Frame, Recorder, Render};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Item {
+    Air,
+    Rock,
+    Sand,
+}
+
+struct Line {
+    points: Vec<Point2>,
+}
+
+impl Line {
+    fn new() -> Line {
+        Line { points: Vec::new() }
+    }
+
+    fn add_point(&mut self, point: Point2) {
+        self.points.push(point);
+    }
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "498,4 -> 498,6 -> 496,6"
+        let source = Source::new(s);
+        let mut line = Line::new();
+        for point in s.split(" -> ") {
+            let (x, y) = source.split_once(point, ",")?;
+            line.add_point(Point2::new(source.parse(x)?, source.parse(y)?));
+        }
+        Ok(line)
+    }
+}
+
+// `x` is the horizontal position and `y` the depth, growing downwards.
+struct Map {
+    map: HashMap<Point2, Item>,
+    max_rock_y: i64,
+    sand_counter: i32,
+}
+
+impl Map {
+    fn new() -> Map {
+        Map {
+            map: HashMap::new(),
+            max_rock_y: 0,
+            sand_counter: 0,
+        }
+    }
+
+    fn add_rocks(&mut self, line: Line) {
+        for i in 1..line.points.len() {
+            let mut x1 = line.points[i - 1].x;
+            let mut x2 = line.points[i].x;
+            if x1 > x2 {
+                (x1, x2) = (x2, x1);
+            }
+            let mut y1 = line.points[i - 1].y;
+            let mut y2 = line.points[i].y;
+            if y1 > y2 {
+                (y1, y2) = (y2, y1);
+            }
+            for x in x1..x2 + 1 {
+                for y in y1..y2 + 1 {
+                    if y > self.max_rock_y {
+                        self.max_rock_y = y;
+                    }
+                    self.map.insert(Point2::new(x, y), Item::Rock);
+                }
+            }
+        }
+    }
+
+    fn get(&self, x: i64, y: i64) -> Item {
+        if y == self.max_rock_y + 2 {
+            return Item::Rock;
+        }
+        if let Some(item) = self.map.get(&Point2::new(x, y)) {
+            return *item;
+        }
+        Item::Air
+    }
+    fn get_point(&self, point: Point2) -> Item {
+        self.get(point.x, point.y)
+    }
+
+    // The sand is pouring into the cave from point 500,0.
+
+    fn spawn_sand(&mut self) -> bool {
+        let mut sand_point = Point2::new(500, 0);
+        if self.get_point(sand_point) != Item::Air {
+            return false;
+        }
+
+        let mut next_sand_point = self.fall_sand(sand_point);
+
+        while next_sand_point != sand_point {
+            sand_point = next_sand_point;
+            next_sand_point = self.fall_sand(sand_point);
+        }
+
+        self.map.insert(sand_point, Item::Sand);
+        self.sand_counter += 1;
+
+        true
+    }
+
+    fn fall_sand(&self, sand_point: Point2) -> Point2 {
+        // maybe go down
+        let down = sand_point + Point2::new(0, 1);
+        if self.get_point(down) == Item::Air {
+            return down;
+        }
+        // maybe go diagonal left
+        let diagonal_left = sand_point + Point2::new(-1, 1);
+        if self.get_point(diagonal_left) == Item::Air {
+            return diagonal_left;
+        }
+        // maybe go diagonal right
+        let diagonal_right = sand_point + Point2::new(1, 1);
+        if self.get_point(diagonal_right) == Item::Air {
+            return diagonal_right;
+        }
+        sand_point
+    }
+}
+
+// The whole pile of sand fits in the triangle between the source and the
+// floor, so the frames keep the same size.
+impl Render for Map {
+    fn render(&self) -> Frame {
+        let floor = self.max_rock_y + 2;
+        let left = 500 - floor - 1;
+        let mut frame = Frame::new(2 * floor as usize + 3, floor as usize + 1);
+        for y in 0..=floor {
+            for x in left..=500 + floor + 1 {
+                let glyph = match self.get(x, y) {
+                    Item::Rock => '#',
+                    Item::Sand => 'o',
+                    Item::Air => '.',
+                };
+                frame.draw(x - left, y, glyph);
+            }
+        }
+        frame
+    }
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    parse_lines::<Line, _>(input)?;
+    Ok(())
+}
+
+// The cave has a floor two levels below the lowest rock (part2).
+pub fn part2<R: BufRead>(input: R) -> Result<i32, Box<dyn Error>> {
+    part2_recorded(input, &mut Recorder::none())
+}
+
+// Like `part2`, recording the cave after every unit of sand.
+pub fn part2_recorded<R: BufRead>(
+    input: R,
+    recorder: &mut Recorder,
+) -> Result<i32, Box<dyn Error>> {
+    let mut map = Map::new();
+    for rocks in parse_lines::<Line, _>(input)? {
+        map.add_rocks(rocks);
+    }
+
+    while map.spawn_sand() {
+        recorder.record(&map)?;
+    }
+
+    Ok(map.sand_counter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Line>(&fuzz::lines(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_common::geometry::Point2;
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::ranges::RangeSet;
+
+// Parses a "x=2, y=18" position within the source.
+fn parse_point(source: &Source, s: &str) -> Result<Point2, ParseError> {
+    let (x, y) = source.split_once(s, ", ")?;
+    Ok(Point2::new(
+        source.parse(source.strip_prefix(x, "x=")?)?,
+        source.parse(source.strip_prefix(y, "y=")?)?,
+    ))
+}
+
+fn beacon_tuning_frequency(beacon: Point2) -> i64 {
+    // multiplying its x coordinate by 4000000 and then adding its y coordinate
+    beacon.x * 4000000 + beacon.y
+}
+
+#[derive(Debug)]
+struct SensorMeasurement {
+    sensor: Point2,
+    beacon: Point2,
+    distance: i64,
+}
+
+impl FromStr for SensorMeasurement {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
+        let source = Source::new(s);
+        let (sensor_str, beacon_str) = source.split_once(s, ": ")?;
+
+        let sensor = parse_point(&source, source.split_once(sensor_str, "at ")?.1)?;
+        let beacon = parse_point(&source, source.split_once(beacon_str, "at ")?.1)?;
+        let distance = sensor.manhattan(beacon);
+
+        Ok(SensorMeasurement {
+            sensor,
+            beacon,
+            distance,
+        })
+    }
+}
+
+impl SensorMeasurement {
+    // Empty when the row is out of reach of the sensor.
+    pub fn sensored_range_for_y(&self, y: i64) -> RangeInclusive<i64> {
+        let x_diretion_length = self.distance - i64::abs(self.sensor.y - y);
+        self.sensor.x - x_diretion_length..=self.sensor.x + x_diretion_length
+    }
+}
+
+struct Map {
+    measurements: Vec<SensorMeasurement>,
+}
+
+impl Map {
+    fn new() -> Map {
+        Map {
+            measurements: Vec::new(),
+        }
+    }
+
+    pub fn add_measurement(&mut self, measurement: SensorMeasurement) {
+        self.measurements.push(measurement);
+    }
+
+    pub fn find_tuning_frequency_of_distress_beacon(&self, search_bound: i64) -> i64 {
+        // note: it should also be possible to find the tuning frequency by just looking at the edges of sensor
+        // measurements, because the distress beacon should be somewhere around the edge
+        // however, since we already have the 'sensored_range_for_y' from part1, let's reuse it and use brute force ftw
+        for y in 0..search_bound {
+            let ranges = self.sensored_range_for_y(y);
+            if let Some(x) = ranges.gaps(0..=search_bound).min() {
+                return beacon_tuning_frequency(Point2::new(x, y));
+            }
+        }
+        0
+    }
+
+    pub fn count_fields_which_cannot_contain_a_beacon(&self, y: i64) -> i64 {
+        let ranges = self.sensored_range_for_y(y);
+        let beacons: RangeSet = self
+            .measurements
+            .iter()
+            .filter(|m| m.beacon.y == y)
+            .map(|m| m.beacon.x..=m.beacon.x)
+            .collect();
+
+        ranges.difference(&beacons).len()
+    }
+
+    fn sensored_range_for_y(&self, y: i64) -> RangeSet {
+        self.measurements
+            .iter()
+            .map(|measurement| measurement.sensored_range_for_y(y))
+            .collect()
+    }
+}
+
+fn load_map<R: BufRead>(input: R) -> Result<Map, Box<dyn Error>> {
+    let mut map = Map::new();
+
+    for measurement in parse_lines(input)? {
+        map.add_measurement(measurement);
+    }
+
+    Ok(map)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_map(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let map = load_map(input)?;
+    Ok(map.count_fields_which_cannot_contain_a_beacon(2000000))
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let map = load_map(input)?;
+    Ok(map.find_tuning_frequency_of_distress_beacon(4000000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<SensorMeasurement>(&fuzz::lines(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
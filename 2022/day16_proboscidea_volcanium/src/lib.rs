@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::random::Rng;
+use aoc_common::search;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+struct Name {
+    a: char,
+    b: char,
+}
+
+impl FromStr for Name {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(a), Some(b), None) => Ok(Name { a, b }),
+            _ => Err(Source::new(s).error(s, "expected a two letter valve name")),
+        }
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.a, self.b)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Valve {
+    name: Name,
+    flow_rate: i32,
+    tunnels: Vec<Name>,
+}
+
+impl FromStr for Valve {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "Valve BB has flow rate=13; tunnels lead to valves CC, AA"
+        let source = Source::new(s);
+        let (valve, tunnels_raw) = source.split_once(s, "; ")?;
+        let (valve_name_raw, flow_rate_raw) = source.split_once(valve, " has flow rate=")?;
+        let name_raw = source.strip_prefix(valve_name_raw, "Valve ")?;
+
+        let tunnels = if let Some((_, name)) = tunnels_raw.split_once("to valve ") {
+            vec![source.parse(name)?]
+        } else {
+            source
+                .split_once(tunnels_raw, "lead to valves ")?
+                .1
+                .split(", ")
+                .map(|name| source.parse(name))
+                .collect::<Result<_, _>>()?
+        };
+
+        Ok(Valve {
+            name: source.parse(name_raw)?,
+            flow_rate: source.parse(flow_rate_raw)?,
+            tunnels,
+        })
+    }
+}
+
+// The open valves are kept in the bits of a `u64`.
+const USEFUL_VALVES_MAX: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    // index of the valve we are at in the list of valves worth opening, or
+    // past its end at the start
+    valve: usize,
+    time: i32,
+    // bit set of the valves opened so far, by index
+    valves_open: u64,
+}
+
+// Finds the order of opening valves that releases the most pressure in
+// `time_max` minutes.
+//
+// Only the valves with a flow rate are worth going to, so the search moves
+// straight from one to the next (along the shortest path through the
+// tunnels) and opens it. This is a Dijkstra search where each minute costs
+// the flow rate of the valves still closed. The cheapest way to the end
+// wastes the least pressure, and so releases the most.
+fn release_the_most_pressure(valves: Vec<Valve>) -> Result<i32, String> {
+    let time_max = 30;
+    let start = Name { a: 'A', b: 'A' };
+    let valves_map: HashMap<Name, Valve> = valves.into_iter().map(|v| (v.name, v)).collect();
+    if !valves_map.contains_key(&start) {
+        return Err(format!("there is no valve {}", start));
+    }
+    for valve in valves_map.values() {
+        if let Some(name) = valve.tunnels.iter().find(|n| !valves_map.contains_key(n)) {
+            return Err(format!(
+                "valve {} leads to unknown valve {}",
+                valve.name, name
+            ));
+        }
+    }
+
+    let mut useful: Vec<&Valve> = valves_map.values().filter(|v| v.flow_rate > 0).collect();
+    useful.sort_by_key(|v| v.name);
+    if useful.len() > USEFUL_VALVES_MAX {
+        return Err(format!(
+            "{} valves have a flow rate, at most {} are supported",
+            useful.len(),
+            USEFUL_VALVES_MAX
+        ));
+    }
+    let flow_rate_total: i32 = useful.iter().map(|v| v.flow_rate).sum();
+
+    // minutes it takes to walk from each useful valve (and from the start,
+    // which comes last) to each useful valve; unreachable ones are too far
+    let distances: Vec<Vec<i32>> = useful
+        .iter()
+        .map(|v| v.name)
+        .chain([start])
+        .map(|from| {
+            let explored = search::bfs([from], |name| valves_map[name].tunnels.clone(), |_| false);
+            useful
+                .iter()
+                .map(|v| explored.cost(&v.name).map_or(time_max, |d| d as i32))
+                .collect()
+        })
+        .collect();
+
+    let flow_rate_open = |valves_open: u64| -> i32 {
+        (0..useful.len())
+            .filter(|i| valves_open & (1 << i) != 0)
+            .map(|i| useful[i].flow_rate)
+            .sum()
+    };
+
+    let successors = |state: &State| {
+        let wasted_per_minute = flow_rate_total - flow_rate_open(state.valves_open);
+        let mut nexts = vec![];
+        for (valve, &distance) in distances[state.valve].iter().enumerate() {
+            // walk there and spend a minute opening it
+            let time = state.time + distance + 1;
+            if state.valves_open & (1 << valve) != 0 || time > time_max {
+                continue;
+            }
+            let next = State {
+                valve,
+                time,
+                valves_open: state.valves_open | (1 << valve),
+            };
+            nexts.push((next, (time - state.time) * wasted_per_minute));
+        }
+        // or just wait for the time to run out
+        let end = State {
+            time: time_max,
+            ..*state
+        };
+        nexts.push((end, (time_max - state.time) * wasted_per_minute));
+        nexts
+    };
+
+    let explored = search::dijkstra(
+        [State {
+            valve: useful.len(),
+            time: 0,
+            valves_open: 0,
+        }],
+        successors,
+        |state| state.time == time_max,
+    );
+
+    Ok(flow_rate_total * time_max - explored.goal_cost().unwrap())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i32, Box<dyn Error>> {
+    let valves = parse_lines(input)?;
+    Ok(release_the_most_pressure(valves)?)
+}
+
+// Puzzle inputs have 15 valves worth opening. The search grows exponentially
+// with their number, so generated inputs have no more of them than it solves
+// quickly, however many valves there are.
+const GENERATED_USEFUL_VALVES_MAX: usize = 15;
+
+// A network of `size` valves (2 to 676), where every valve can be reached
+// from `AA`. About a quarter of them have a flow rate, up to
+// `GENERATED_USEFUL_VALVES_MAX`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let letters = 'A'..='Z';
+    let mut names: Vec<Name> = letters
+        .clone()
+        .flat_map(|a| letters.clone().map(move |b| Name { a, b }))
+        .filter(|&name| name != Name { a: 'A', b: 'A' })
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(2, 676) - 1);
+    names.insert(0, Name { a: 'A', b: 'A' });
+
+    // a random tree joining all the valves, and a few more tunnels
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+    let mut join = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..names.len() {
+        join(i, rng.below(i));
+    }
+    for i in 0..names.len() {
+        if rng.chance(30) {
+            join(i, rng.below(names.len()));
+        }
+    }
+
+    let mut useful = 0;
+    let mut valves: Vec<String> = names
+        .iter()
+        .zip(&tunnels)
+        .enumerate()
+        .map(|(i, (name, tunnels))| {
+            let flow_rate = if i > 0 && useful < GENERATED_USEFUL_VALVES_MAX && rng.chance(25) {
+                useful += 1;
+                rng.between(3, 25)
+            } else {
+                0
+            };
+            let tunnels: Vec<String> = tunnels.iter().map(|&t| names[t].to_string()).collect();
+            match tunnels.as_slice() {
+                [tunnel] => format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}\n",
+                    name, flow_rate, tunnel
+                ),
+                _ => format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}\n",
+                    name,
+                    flow_rate,
+                    tunnels.join(", ")
+                ),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut valves);
+    valves.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+    use std::fs::File as FSFile;
+    use std::io::BufReader;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn test_find_most_pressure_we_can_release_dev() {
+        let file = FSFile::open("./input.dev").expect("input file should exist");
+        assert_eq!(part1(BufReader::new(file)).unwrap(), 1651);
+    }
+
+    #[test]
+    fn test_find_most_pressure_we_can_release_prod() {
+        let file = FSFile::open("./input.prod").expect("input file should exist");
+        assert_eq!(part1(BufReader::new(file)).unwrap(), 1653);
+    }
+
+    #[test]
+    fn too_many_useful_valves_are_rejected() {
+        // AA leads to a row of 65 valves with a flow rate
+        let names: Vec<String> = ('B'..='D')
+            .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+            .take(USEFUL_VALVES_MAX + 1)
+            .collect();
+        let mut input = format!(
+            "Valve AA has flow rate=0; tunnel leads to valve {}\n",
+            names[0]
+        );
+        for (i, name) in names.iter().enumerate() {
+            let previous = if i == 0 { "AA" } else { &names[i - 1] };
+            let tunnels = match names.get(i + 1) {
+                Some(next) => format!("tunnels lead to valves {}, {}", previous, next),
+                None => format!("tunnel leads to valve {}", previous),
+            };
+            input += &format!("Valve {} has flow rate=1; {}\n", name, tunnels);
+        }
+        let error = part1(input.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "65 valves have a flow rate, at most 64 are supported"
+        );
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::round_trips::<Name>(&["AA", "BB", "JJ"]);
+        fuzz::parses::<Valve>(&fuzz::lines(EXAMPLE));
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for (seed, size) in [(0, 2), (1, 10), (2, 40), (3, 676)] {
+            let input = generate(seed, size);
+            assert_eq!(input.lines().count(), size);
+            part1(input.as_bytes()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::cycle::track_cycle;
+use aoc_common::geometry::Point2;
+use aoc_common::input::{lines, InputError};
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::random::Rng;
+use aoc_common::render::{Frame, Recorder, Render};
+use aoc_common::trace::debug;
+
+#[derive(Debug, Clone)]
+enum RockShape {
+    Minus,
+    Cross,
+    IL,
+    Vertical,
+    Block,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Field {
+    Air,
+    Rock,
+    Unknown,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::Air => write!(f, "."),
+            Field::Rock => write!(f, "#"),
+            Field::Unknown => write!(f, "!"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum HotGasJet {
+    Left,
+    Right,
+}
+
+impl FromStr for HotGasJet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ">" => Ok(HotGasJet::Right),
+            "<" => Ok(HotGasJet::Left),
+            _ => Err(Source::new(s).error(s, "expected '<' or '>'")),
+        }
+    }
+}
+
+// A rock on its way down. The chamber lies on its side: `x` is the height and
+// `y` the distance from the left wall.
+#[derive(Debug, Clone)]
+struct Rock {
+    shape: RockShape,
+    position: Point2,
+}
+
+impl Rock {
+    fn new(position: Point2, shape: RockShape) -> Self {
+        Self { position, shape }
+    }
+
+    pub fn move_by(&mut self, hot_gas_jet: &HotGasJet) {
+        match hot_gas_jet {
+            HotGasJet::Left => self.position.y -= 1,
+            HotGasJet::Right => self.position.y += 1,
+        }
+    }
+
+    pub fn field_coordinates(&self) -> Vec<Point2> {
+        let offsets: &[(i64, i64)] = match self.shape {
+            RockShape::Minus => &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            RockShape::Cross => &[(-1, 0), (0, 1), (-1, 1), (-2, 1), (-1, 2)],
+            RockShape::IL => &[(0, 2), (-1, 2), (-2, 2), (-2, 1), (-2, 0)],
+            RockShape::Vertical => &[(0, 0), (-1, 0), (-2, 0), (-3, 0)],
+            RockShape::Block => &[(0, 1), (0, 0), (-1, 0), (-1, 1)],
+        };
+        offsets
+            .iter()
+            .map(|&offset| self.position + Point2::from(offset))
+            .collect()
+    }
+}
+
+// Deeper than any rock falls into a column.
+const SURFACE_DEPTH_MAX: i64 = 100;
+
+struct Game {
+    gas_jet_moves: Vec<HotGasJet>,
+    map: VecDeque<Vec<Field>>,
+
+    last_rock_shape: usize,
+    last_jet_move: usize,
+    max_occupied_x: i32,
+    // highest rock of every column, -1 while it has none
+    column_tops: [i32; 7],
+    map_x_shift: usize,
+}
+
+impl Game {
+    fn new() -> Self {
+        Self {
+            gas_jet_moves: Vec::new(),
+            map: VecDeque::new(),
+            last_rock_shape: 0,
+            last_jet_move: 0,
+            map_x_shift: 0,
+            max_occupied_x: -1,
+            column_tops: [-1; 7],
+        }
+    }
+
+    pub fn add_gas_jet(&mut self, gas_jet: HotGasJet) {
+        self.gas_jet_moves.push(gas_jet);
+    }
+
+    pub fn simulate_falling_rock(&mut self) {
+        let mut new_rock = self.get_new_rock();
+        loop {
+            self.rock_push_by_hot_gas(&mut new_rock);
+            if !self.rock_move_downward(&mut new_rock) {
+                break;
+            }
+        }
+        self.rock_save_in_map(new_rock);
+
+        self.maybe_prune_downwards_memory();
+    }
+
+    pub fn tower_height(&self) -> i32 {
+        self.max_occupied_x + 1
+    }
+
+    fn maybe_prune_downwards_memory(&mut self) {
+        let max_rows_in_memory = 1_000_000;
+        while self.map.len() > max_rows_in_memory {
+            self.map.pop_front();
+            self.map_x_shift += 1;
+        }
+    }
+
+    fn rock_push_by_hot_gas(&mut self, rock: &mut Rock) {
+        let hot_gas = self.get_hot_gas_jet_move();
+        let mut new_rock = rock.clone();
+        new_rock.move_by(hot_gas);
+        if self.rock_is_invalid(&new_rock) {
+            return;
+        }
+        *rock = new_rock;
+    }
+
+    fn get(&self, Point2 { x, y }: Point2) -> &Field {
+        // Check map bounds first.
+        if !(0..7).contains(&y) || (x < 0) {
+            return &Field::Unknown;
+        }
+        if x < self.map_x_shift as i64 {
+            panic!("trying to access a prunned field down");
+        }
+        let map_x = x as usize - self.map_x_shift;
+        if map_x >= self.map.len() {
+            return &Field::Air;
+        }
+        // Attempt to get the field from map vec.
+        self.map.get(map_x).unwrap().get(y as usize).unwrap()
+    }
+
+    fn save(&mut self, Point2 { x, y }: Point2, field: Field) {
+        let (x, y) = (x as usize, y as usize);
+        let map_x = x - self.map_x_shift;
+        while self.map.len() <= map_x {
+            self.map.push_back(vec![Field::Air; 7]);
+        }
+        if field == Field::Rock {
+            self.max_occupied_x = self.max_occupied_x.max(x as i32);
+            self.column_tops[y] = self.column_tops[y].max(x as i32);
+        }
+        self.map[map_x][y] = field;
+    }
+
+    fn rock_move_downward(&self, rock: &mut Rock) -> bool {
+        let mut rock_down = rock.clone();
+        rock_down.position.x -= 1;
+        if self.rock_is_invalid(&rock_down) {
+            return false;
+        }
+        *rock = rock_down;
+        true
+    }
+
+    fn rock_save_in_map(&mut self, rock: Rock) {
+        for point in rock.field_coordinates() {
+            self.save(point, Field::Rock);
+        }
+    }
+
+    fn rock_is_invalid(&self, rock: &Rock) -> bool {
+        for point in rock.field_coordinates() {
+            let field = self.get(point);
+            match field {
+                Field::Rock => return true,
+                Field::Unknown => return true,
+                Field::Air => continue,
+            }
+        }
+        false
+    }
+
+    fn get_new_rock(&mut self) -> Rock {
+        let shape = self.get_new_rock_shape();
+        let position = self.new_rock_coordinates(&shape);
+        Rock::new(position, shape)
+    }
+
+    // Where `position` of a new rock is for its lowest part to be three rows
+    // above the tower and its left side two columns away from the wall.
+    fn new_rock_coordinates(&self, shape: &RockShape) -> Point2 {
+        let lowest = Point2::new(self.max_occupied_x as i64 + 4, 2);
+        match shape {
+            RockShape::Minus => lowest,
+            RockShape::Cross => lowest + Point2::new(2, 0),
+            RockShape::IL => lowest + Point2::new(2, 0),
+            RockShape::Vertical => lowest + Point2::new(3, 0),
+            RockShape::Block => lowest + Point2::new(1, 0),
+        }
+    }
+
+    fn get_new_rock_shape(&mut self) -> RockShape {
+        self.last_rock_shape = (self.last_rock_shape + 1) % 5;
+        match self.last_rock_shape {
+            1 => RockShape::Minus,
+            2 => RockShape::Cross,
+            3 => RockShape::IL,
+            4 => RockShape::Vertical,
+            0 => RockShape::Block,
+            _ => unreachable!("mod, so can't have other values"),
+        }
+    }
+
+    fn get_hot_gas_jet_move(&mut self) -> &HotGasJet {
+        let hot_gas_jet = self.gas_jet_moves.get(self.last_jet_move).unwrap();
+        self.last_jet_move = (self.last_jet_move + 1) % self.gas_jet_moves.len();
+        hot_gas_jet
+    }
+
+    // What decides how the next rocks fall: the depth of every column below
+    // the top of the tower, the next rock shape and the next gas jet. Depths
+    // are capped, as columns the jets keep rocks away from (on constant jets)
+    // would otherwise get deeper forever and never repeat.
+    fn surface(&self) -> ([i64; 7], usize, usize) {
+        let depths = self
+            .column_tops
+            .map(|top| ((self.max_occupied_x - top) as i64).min(SURFACE_DEPTH_MAX));
+        (depths, self.last_rock_shape, self.last_jet_move)
+    }
+}
+
+// Rows of the tower shown in a frame, counting down from its top.
+const FRAME_ROWS: i32 = 40;
+
+// The top of the tower, the chamber is drawn on its side: `x` (the height)
+// goes up the frame.
+impl Render for Game {
+    fn render(&self) -> Frame {
+        let top = self.tower_height() + 3;
+        let mut frame = Frame::new(7, FRAME_ROWS as usize);
+        for row in 0..FRAME_ROWS {
+            let x = top - row;
+            if x < self.map_x_shift as i32 {
+                break;
+            }
+            for y in 0..7 {
+                let glyph = match self.get(Point2::new(x as i64, y)) {
+                    Field::Rock => '#',
+                    Field::Air => '.',
+                    Field::Unknown => '!',
+                };
+                frame.draw(y, row as i64, glyph);
+            }
+        }
+        frame
+    }
+}
+
+fn load_game<R: BufRead>(input: R) -> Result<Game, Box<dyn Error>> {
+    let mut game = Game::new();
+
+    for line in lines(input) {
+        let (n, line) = line?;
+        let source = Source::new(&line);
+        for (i, c) in line.char_indices() {
+            let gas_jet = source
+                .parse(&line[i..i + c.len_utf8()])
+                .map_err(|e| InputError::parse(n, e))?;
+            game.add_gas_jet(gas_jet);
+        }
+    }
+    if game.gas_jet_moves.is_empty() {
+        return Err("there are no jets of hot gas".into());
+    }
+
+    Ok(game)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_game(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i32, Box<dyn Error>> {
+    part1_recorded(input, &mut Recorder::none())
+}
+
+// Like `part1`, recording the top of the tower after every rock.
+pub fn part1_recorded<R: BufRead>(
+    input: R,
+    recorder: &mut Recorder,
+) -> Result<i32, Box<dyn Error>> {
+    let mut game = load_game(input)?;
+    for _ in 0..2022 {
+        game.simulate_falling_rock();
+        recorder.record(&game)?;
+    }
+    Ok(game.tower_height())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let mut game = load_game(input)?;
+    // The surface of the tower repeats within a few thousand rocks, once it
+    // does the height grows by the same amount with every round.
+    let tracked = track_cycle(
+        &mut game,
+        100_000,
+        |game| {
+            game.simulate_falling_rock();
+            true
+        },
+        Game::surface,
+        |game| game.tower_height() as i64,
+    )
+    .ok_or("the tower does not repeat itself")?;
+    debug!(
+        "the surface repeats every {} rocks from rock {} on",
+        tracked.cycle.length, tracked.cycle.start
+    );
+    Ok(tracked.metric_at(1_000_000_000_000))
+}
+
+// A pattern of `size` random jets, at least one.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut jets: String = (0..size.max(1)).map(|_| *rng.pick(&['<', '>'])).collect();
+    jets.push('\n');
+    jets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<HotGasJet>(&["<", ">"]);
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+        for input in ["", "\n", "\n\n"] {
+            assert!(parse(input.as_bytes()).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for (seed, size) in [(0, 0), (1, 40), (2, 500)] {
+            let input = generate(seed, size);
+            let height = part1(input.as_bytes()).unwrap();
+            assert!(height > 0 && height <= 2022 * 4);
+            part2(input.as_bytes()).unwrap();
+        }
+    }
+}
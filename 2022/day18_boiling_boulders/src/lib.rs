@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::BufRead;
+
+use aoc_common::geometry::Point3;
+use aoc_common::input::{lines, InputError};
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::search;
+
+type Cube = Point3;
+
+fn nexts_ordered(cube: &Cube) -> Vec<Cube> {
+    vec![
+        *cube + Cube::new(1, 0, 0),
+        *cube + Cube::new(0, 1, 0),
+        *cube + Cube::new(0, 0, 1),
+    ]
+}
+
+#[derive(Debug)]
+struct Lava {
+    cubes: HashSet<Cube>,
+}
+
+impl Lava {
+    fn new() -> Self {
+        Self {
+            cubes: HashSet::new(),
+        }
+    }
+
+    pub fn add_cube(&mut self, cube: Cube) {
+        self.cubes.insert(cube);
+    }
+
+    pub fn surface_area(&mut self) -> u32 {
+        let mut count: u32 = self.cubes.len() as u32 * 6;
+
+        let mut cl: Vec<&Cube> = self.cubes.iter().collect();
+        cl.sort();
+
+        for cube in cl {
+            for cube_next in nexts_ordered(cube) {
+                if self.cubes.contains(&cube_next) {
+                    count -= 2;
+                }
+            }
+        }
+
+        count
+    }
+
+    pub fn surface_external_area(&self) -> u32 {
+        let outside_air = self.outside_air();
+
+        self.cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours6())
+            .filter(|cube| outside_air.contains(cube))
+            .count() as u32
+    }
+
+    // Flood fills the air around the lava, within a box one cube larger than
+    // the lava on every side so that the air can flow all around it.
+    fn outside_air(&self) -> HashSet<Cube> {
+        let min = Cube::new(-1, -1, -1);
+        let max = self.cubes.iter().fold(Cube::ORIGIN, |max, cube| {
+            Cube::new(max.x.max(cube.x), max.y.max(cube.y), max.z.max(cube.z))
+        }) + Cube::new(1, 1, 1);
+        let in_box = |cube: &Cube| {
+            (min.x..=max.x).contains(&cube.x)
+                && (min.y..=max.y).contains(&cube.y)
+                && (min.z..=max.z).contains(&cube.z)
+        };
+
+        search::bfs(
+            [min],
+            |cube| {
+                cube.neighbours6()
+                    .into_iter()
+                    .filter(|next| in_box(next) && !self.cubes.contains(next))
+            },
+            |_| false,
+        )
+        .states()
+        .map(|(cube, _)| *cube)
+        .collect()
+    }
+}
+
+// Parses a "2,2,2" cube.
+fn parse_cube(s: &str) -> Result<Cube, ParseError> {
+    let source = Source::new(s);
+    let (x, yz) = source.split_once(s, ",")?;
+    let (y, z) = source.split_once(yz, ",")?;
+    Ok(Cube::new(
+        source.parse(x)?,
+        source.parse(y)?,
+        source.parse(z)?,
+    ))
+}
+
+fn load_lava<R: BufRead>(input: R) -> Result<Lava, Box<dyn Error>> {
+    let mut lava = Lava::new();
+
+    for line in lines(input) {
+        let (n, line) = line?;
+        lava.add_cube(parse_cube(&line).map_err(|e| InputError::parse(n, e))?);
+    }
+
+    Ok(lava)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_lava(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    Ok(load_lava(input)?.surface_area())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    Ok(load_lava(input)?.surface_external_area())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
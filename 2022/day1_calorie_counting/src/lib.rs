@@ -0,0 +1,103 @@
+use aoc_common::input::{lines, InputError};
+use std::error::Error;
+use std::io::BufRead;
+
+type Calories = u64;
+
+struct SantaTeam {
+    elves_top_n: Vec<Calories>,
+}
+
+impl SantaTeam {
+    fn new<R: BufRead>(input: R, n: usize) -> Result<Self, Box<dyn Error>> {
+        let mut team = Self {
+            elves_top_n: vec![0; n],
+        };
+
+        // Every elve's inventory is a record of calories, one item per line,
+        // summed up as the lines come so that no inventory is held in memory.
+        let mut elve_current: Option<Calories> = None;
+        for line in lines(input) {
+            let (line, calories) = line?;
+            if calories.trim().is_empty() {
+                if let Some(elve) = elve_current.take() {
+                    team.add(elve);
+                }
+                continue;
+            }
+            *elve_current.get_or_insert(0) += calories
+                .parse::<Calories>()
+                .map_err(|e| InputError::parse(line, e))?;
+        }
+        // The last inventory needs no blank line after it.
+        if let Some(elve) = elve_current {
+            team.add(elve);
+        }
+
+        Ok(team)
+    }
+
+    fn add(&mut self, elve_current: Calories) {
+        let elve_worst = self
+            .elves_top_n
+            .last_mut()
+            .expect("team must have at least one elve");
+        // If the worst Elve of our TopN group gathered less Calories than current Elve:
+        if *elve_worst < elve_current {
+            // Replace the 'worst Elve' with the current Elve and sort the topN again.
+            *elve_worst = elve_current;
+            self.elves_top_n.sort_by(|a, b| b.cmp(a));
+        }
+    }
+
+    fn sum_top_n_calories(&self, n: usize) -> Result<Calories, &'static str> {
+        if n > self.elves_top_n.len() {
+            return Err(
+                "n cannot be larger than number of top team passed in the SantaTeam::new constructor",
+            );
+        }
+        let mut calories: Calories = 0;
+        for i in 0..=n - 1 {
+            calories += self.elves_top_n[i];
+        }
+        Ok(calories)
+    }
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<Calories, Box<dyn Error>> {
+    let team = SantaTeam::new(input, 1)?;
+    Ok(team.sum_top_n_calories(1)?)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<Calories, Box<dyn Error>> {
+    let team = SantaTeam::new(input, 3)?;
+    Ok(team.sum_top_n_calories(3)?)
+}
+
+#[cfg(test)]
+fn input_file() -> std::io::BufReader<std::fs::File> {
+    std::io::BufReader::new(std::fs::File::open("./input.prod").expect("input file should exist"))
+}
+
+#[test]
+fn top_1() {
+    let team = SantaTeam::new(input_file(), 1).unwrap();
+    assert_eq!(67658, team.sum_top_n_calories(1).unwrap());
+}
+
+#[test]
+fn top_3() {
+    let team = SantaTeam::new(input_file(), 3).unwrap();
+    assert_eq!(200158, team.sum_top_n_calories(3).unwrap());
+}
+
+#[test]
+fn last_elve_needs_no_blank_line() {
+    let team = SantaTeam::new("\n1\n2\n\n\n4\n\n5\n6".as_bytes(), 3).unwrap();
+    assert_eq!(team.elves_top_n, vec![11, 4, 3]);
+    let team = SantaTeam::new("1\n2\n\n".as_bytes(), 1).unwrap();
+    assert_eq!(team.elves_top_n, vec![3]);
+    // no elves, no calories
+    let team = SantaTeam::new("".as_bytes(), 1).unwrap();
+    assert_eq!(team.elves_top_n, vec![0]);
+}
@@ -1,6 +1,7 @@
-use std::fs::File as FSFile;
-use std::io::{BufRead, BufReader};
+use aoc_common::input::InputSource;
+use std::io::BufRead;
 
+#[allow(dead_code)]
 struct Coordinates {
     values: Vec<i32>,
 }
@@ -13,10 +14,12 @@ impl Coordinates {
 
 fn main() {
     let mut vs = Vec::new();
-    let file = FSFile::open("./input.dev").expect("input file should exist");
-    for line_wrapped in BufReader::new(file).lines() {
+    let input = InputSource::from_args(env!("CARGO_MANIFEST_DIR"), "dev")
+        .open()
+        .expect("input file should exist");
+    for line_wrapped in input.lines() {
         vs.push(line_wrapped.unwrap().parse::<i32>().unwrap());
     }
 
-    let mut coordinates = Coordinates::new(vs);
+    let _coordinates = Coordinates::new(vs);
 }
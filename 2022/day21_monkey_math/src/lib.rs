@@ -0,0 +1,485 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::BufRead;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::random::Rng;
+use aoc_common::trace::debug;
+
+type Name = String;
+
+#[derive(Debug)]
+enum Expression {
+    Val(i64),
+    Str(String),
+}
+
+#[derive(Debug, Hash, Eq, Clone, PartialEq)]
+enum Operation {
+    Value(i64),
+    Add(Name, Name),
+    Mul(Name, Name),
+    Sub(Name, Name),
+    Div(Name, Name),
+}
+
+impl Operation {
+    fn variables(&self) -> Option<(Name, Name)> {
+        match self {
+            Operation::Value(_) => None,
+            Operation::Add(a, b) => Some((a.to_string(), b.to_string())),
+            Operation::Mul(a, b) => Some((a.to_string(), b.to_string())),
+            Operation::Sub(a, b) => Some((a.to_string(), b.to_string())),
+            Operation::Div(a, b) => Some((a.to_string(), b.to_string())),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn exec(&self, context: &HashMap<Name, i64>) -> i64 {
+        match self {
+            Operation::Value(v) => *v,
+            Operation::Add(a, b) => context.get(a).unwrap() + context.get(b).unwrap(),
+            Operation::Mul(a, b) => context.get(a).unwrap() * context.get(b).unwrap(),
+            Operation::Sub(a, b) => context.get(a).unwrap() - context.get(b).unwrap(),
+            Operation::Div(a, b) => context.get(a).unwrap() / context.get(b).unwrap(),
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = s.parse::<i64>() {
+            return Ok(Operation::Value(value));
+        }
+        for operator in [" + ", " - ", " * ", " / "] {
+            if let Some((a, b)) = s.split_once(operator) {
+                let (a, b) = (a.to_string(), b.to_string());
+                return Ok(match operator {
+                    " + " => Operation::Add(a, b),
+                    " - " => Operation::Sub(a, b),
+                    " * " => Operation::Mul(a, b),
+                    _ => Operation::Div(a, b),
+                });
+            }
+        }
+        Err(Source::new(s).error(s, "expected a number or an operation like 'a + b'"))
+    }
+}
+
+#[derive(Debug, Hash, Eq, PartialEq)]
+struct Monkey {
+    name: String,
+    operation: Operation,
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (name, operation_raw) = source.split_once(s, ": ")?;
+        let operation = source.parse(operation_raw)?;
+        Ok(Monkey {
+            name: name.to_string(),
+            operation,
+        })
+    }
+}
+
+#[derive(PartialEq)]
+struct TreeNode {
+    pub value: Option<i64>,
+
+    name: String,
+    pub parent: Option<Rc<RefCell<TreeNode>>>,
+
+    pub operation: Option<Operation>,
+    pub left: Option<Rc<RefCell<TreeNode>>>,
+    pub right: Option<Rc<RefCell<TreeNode>>>,
+}
+
+impl TreeNode {
+    pub fn new(name: String) -> TreeNode {
+        TreeNode {
+            name,
+            value: None,
+            parent: None,
+            operation: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    pub fn value_without(&self, name: String) -> Expression {
+        if self.name == name {
+            return Expression::Str(name);
+        }
+        if let Operation::Value(v) = self.operation.as_ref().unwrap() {
+            return Expression::Val(*v);
+        }
+
+        let a = self
+            .left
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .value_without(name.clone());
+        let b = self.right.as_ref().unwrap().borrow().value_without(name);
+
+        if let Expression::Val(a) = a {
+            if let Expression::Val(b) = b {
+                let v = match self.operation.as_ref().unwrap() {
+                    Operation::Value(v) => *v,
+                    Operation::Add(_, _) => a + b,
+                    Operation::Sub(_, _) => a - b,
+                    Operation::Mul(_, _) => a * b,
+                    Operation::Div(_, _) => a / b,
+                };
+                return Expression::Val(v);
+            }
+        }
+
+        let a = match a {
+            Expression::Val(v) => v.to_string(),
+            Expression::Str(s) => s,
+        };
+        let b = match b {
+            Expression::Val(v) => v.to_string(),
+            Expression::Str(s) => s,
+        };
+
+        let op = match self.operation.as_ref().unwrap() {
+            Operation::Value(_) => unreachable!("invalid data"),
+            Operation::Add(_, _) => "+",
+            Operation::Sub(_, _) => "-",
+            Operation::Mul(_, _) => "*",
+            Operation::Div(_, _) => "/",
+        };
+
+        Expression::Str(format!("({}{}{})", a, op, b))
+    }
+
+    pub fn value_equals(&self, x_name: Name, equal: i64) -> i64 {
+        if self.name == x_name {
+            return equal;
+        }
+
+        let mut left: Option<i64> = None;
+        let mut right: Option<i64> = None;
+
+        if let Expression::Val(v) = self
+            .left
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .value_without(x_name.clone())
+        {
+            left = Some(v);
+        }
+        if let Expression::Val(v) = self
+            .right
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .value_without(x_name.clone())
+        {
+            right = Some(v);
+        }
+
+        if left.is_none() && right.is_none() {
+            return 0;
+        }
+
+        if let (Some(left), None) = (left, right) {
+            // equals = left OP right
+            let new_equals = match self.operation.as_ref().unwrap() {
+                Operation::Value(_) => unreachable!("invalid data"),
+                // right = equals - left
+                Operation::Add(_, _) => equal - left,
+                // right = left - equals
+                Operation::Sub(_, _) => left - equal,
+                // right = equals / left
+                Operation::Mul(_, _) => equal / left,
+                // right = left / equals
+                Operation::Div(_, _) => left / equal,
+            };
+            return self
+                .right
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .value_equals(x_name.clone(), new_equals);
+        }
+
+        if let (None, Some(right)) = (left, right) {
+            // equals = left OP right
+            let new_equals = match self.operation.as_ref().unwrap() {
+                Operation::Value(_) => unreachable!("invalid data"),
+                // left = equals - right
+                Operation::Add(_, _) => equal - right,
+                // left = right + equals
+                Operation::Sub(_, _) => equal + right,
+                // left = equals / right
+                Operation::Mul(_, _) => equal / right,
+                // left = equals * right
+                Operation::Div(_, _) => equal * right,
+            };
+            return self
+                .left
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .value_equals(x_name.clone(), new_equals);
+        }
+
+        0
+    }
+
+    pub fn find_value(&self, name: String) -> i64 {
+        let a = self
+            .left
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .value_without(name.clone());
+        if let Expression::Val(a) = a {
+            return self.right.as_ref().unwrap().borrow().value_equals(name, a);
+        }
+
+        let b = self
+            .right
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .value_without(name.clone());
+        if let Expression::Val(b) = b {
+            return self.left.as_ref().unwrap().borrow().value_equals(name, b);
+        }
+
+        0
+    }
+
+    pub fn value(&self) -> i64 {
+        if let Some(v) = self.value {
+            v
+        } else {
+            if let Operation::Value(v) = self.operation.as_ref().unwrap() {
+                return *v;
+            }
+
+            let a = self.left.as_ref().unwrap().borrow().value();
+            let b = self.right.as_ref().unwrap().borrow().value();
+
+            match self.operation.as_ref().unwrap() {
+                Operation::Value(v) => *v,
+                Operation::Add(_, _) => a + b,
+                Operation::Sub(_, _) => a - b,
+                Operation::Mul(_, _) => a * b,
+                Operation::Div(_, _) => a / b,
+            }
+        }
+    }
+}
+
+struct MonkeyTreeBuilder {
+    monkeys: HashMap<Name, Monkey>,
+}
+
+impl MonkeyTreeBuilder {
+    fn new() -> Self {
+        MonkeyTreeBuilder {
+            monkeys: HashMap::new(),
+        }
+    }
+
+    fn add_monkey(&mut self, monkey: Monkey) {
+        self.monkeys.insert(monkey.name.clone(), monkey);
+    }
+
+    pub fn build(&self, name: Name) -> Result<Rc<RefCell<TreeNode>>, String> {
+        let root = Rc::new(RefCell::new(TreeNode::new(name.clone())));
+        let monkey = self
+            .monkeys
+            .get(&name)
+            .ok_or(format!("there is no monkey '{}'", name))?;
+
+        root.borrow_mut().operation = Some(monkey.operation.clone());
+        if let Some((left, right)) = monkey.operation.variables() {
+            root.borrow_mut().left = Some(self.build(left)?);
+            root.borrow_mut().right = Some(self.build(right)?);
+        }
+
+        Ok(root)
+    }
+}
+
+fn build_tree<R: BufRead>(input: R) -> Result<Rc<RefCell<TreeNode>>, Box<dyn Error>> {
+    let mut tree_builder = MonkeyTreeBuilder::new();
+
+    for monkey in parse_lines(input)? {
+        tree_builder.add_monkey(monkey);
+    }
+
+    debug!("{} monkeys", tree_builder.monkeys.len());
+    Ok(tree_builder.build("root".to_string())?)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    build_tree(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let tree = build_tree(input)?;
+    let value = tree.borrow().value();
+    Ok(value)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let tree = build_tree(input)?;
+    let value = tree.borrow().find_value("humn".to_string());
+    Ok(value)
+}
+
+// Monkeys of a generated input: their name, the number they yell, the job
+// giving that number and whether `humn` is among the monkeys it depends on.
+struct Generated {
+    name: Name,
+    value: i64,
+    job: String,
+    humn: bool,
+}
+
+// A tree of about `size` monkeys (5 at least). All divisions are exact and
+// none divides by zero, and `humn` is never a divisor, so that the number
+// `humn` yells is the only one that makes both sides of `root` equal.
+pub fn generate(seed: u64, size: usize) -> String {
+    // products are kept well below the limits of `i64`
+    const LIMIT: i64 = 1_000_000_000_000;
+
+    let mut rng = Rng::new(seed);
+    let mut taken: HashSet<Name> = ["root", "humn"].map(String::from).into();
+    let name_len = (4..).find(|&n| 26usize.pow(n) >= 4 * size).unwrap() as usize;
+    let mut new_name = |rng: &mut Rng| loop {
+        let name: Name = (0..name_len)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let mut monkeys = Vec::new();
+    let leaves = (size.max(5) - 1) / 2;
+    let mut pool: Vec<Generated> = (1..leaves)
+        .map(|_| {
+            let value = rng.between(1, 20);
+            Generated {
+                name: new_name(&mut rng),
+                value,
+                job: value.to_string(),
+                humn: false,
+            }
+        })
+        .collect();
+    let humn = rng.between(1, 4000);
+    pool.push(Generated {
+        name: "humn".to_string(),
+        value: humn,
+        job: humn.to_string(),
+        humn: true,
+    });
+
+    while pool.len() > 2 {
+        let a = pool.swap_remove(rng.below(pool.len()));
+        let b = pool.swap_remove(rng.below(pool.len()));
+        let product = a.value.checked_mul(b.value).filter(|p| p.abs() <= LIMIT);
+        let mut operations = vec![('+', a.value + b.value), ('-', a.value - b.value)];
+        if let Some(product) = product {
+            // dividing by zero undoes a multiplication by zero
+            if !(a.humn && b.value == 0 || b.humn && a.value == 0) {
+                operations.push(('*', product));
+            }
+        }
+        if !b.humn && b.value != 0 && a.value % b.value == 0 {
+            operations.push(('/', a.value / b.value));
+        }
+        let &(operator, value) = rng.pick(&operations);
+        pool.push(Generated {
+            name: new_name(&mut rng),
+            value,
+            job: format!("{} {} {}", a.name, operator, b.name),
+            humn: a.humn || b.humn,
+        });
+        monkeys.extend([a, b]);
+    }
+
+    // the side without `humn` is evened up with the other one
+    let (a, b) = (pool.pop().unwrap(), pool.pop().unwrap());
+    let (known, unknown) = if a.humn { (b, a) } else { (a, b) };
+    let difference = unknown.value - known.value;
+    let leveller = Generated {
+        name: new_name(&mut rng),
+        value: difference.abs(),
+        job: difference.abs().to_string(),
+        humn: false,
+    };
+    let levelled = Generated {
+        name: new_name(&mut rng),
+        value: unknown.value,
+        job: if difference >= 0 {
+            format!("{} + {}", known.name, leveller.name)
+        } else {
+            format!("{} - {}", known.name, leveller.name)
+        },
+        humn: false,
+    };
+    let sides = if rng.chance(50) {
+        [&unknown.name, &levelled.name]
+    } else {
+        [&levelled.name, &unknown.name]
+    };
+    let root = format!("root: {} + {}\n", sides[0], sides[1]);
+    monkeys.extend([known, unknown, leveller, levelled]);
+
+    let mut lines: Vec<String> = monkeys
+        .iter()
+        .map(|monkey| format!("{}: {}\n", monkey.name, monkey.job))
+        .chain([root])
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Monkey>(&fuzz::lines(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for (seed, size) in [(0, 1), (1, 15), (2, 2000)] {
+            let input = generate(seed, size);
+            part1(input.as_bytes()).unwrap();
+            let humn = input
+                .lines()
+                .find_map(|line| line.strip_prefix("humn: "))
+                .unwrap();
+            assert_eq!(part2(input.as_bytes()).unwrap().to_string(), humn);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use aoc_common::input::process;
+use aoc_common::parse::{ParseError, Source};
+use std::error::Error;
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    fn new(source: &Source, v: &str) -> Result<Self, ParseError> {
+        match v {
+            "A" | "X" => Ok(Shape::Rock),
+            "B" | "Y" => Ok(Shape::Paper),
+            "C" | "Z" => Ok(Shape::Scissors),
+            _ => Err(source.error(v, "expected a shape (A, B, C, X, Y or Z)")),
+        }
+    }
+
+    fn score(&self) -> u64 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum RoundOutcome {
+    Lost,
+    Draw,
+    Won,
+}
+
+impl RoundOutcome {
+    fn new(source: &Source, v: &str) -> Result<Self, ParseError> {
+        match v {
+            "X" => Ok(RoundOutcome::Lost),
+            "Y" => Ok(RoundOutcome::Draw),
+            "Z" => Ok(RoundOutcome::Won),
+            _ => Err(source.error(v, "expected a round outcome (X, Y or Z)")),
+        }
+    }
+
+    fn score(&self) -> u64 {
+        match self {
+            RoundOutcome::Draw => 3,
+            RoundOutcome::Won => 6,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Round {
+    player1: Shape,
+    player2: Shape,
+}
+
+impl Round {
+    fn new(player1: Shape, player2: Shape) -> Self {
+        Self { player1, player2 }
+    }
+
+    // "A Y": the shape of the opponent, then ours.
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let source = Source::new(line);
+        let (opponent, player) = source.split_once(line, " ")?;
+        Ok(Round::new(
+            Shape::new(&source, player)?,
+            Shape::new(&source, opponent)?,
+        ))
+    }
+
+    pub fn score(&self) -> u64 {
+        self.player1_outcome().score() + self.player1.score()
+    }
+
+    fn player1_outcome(&self) -> RoundOutcome {
+        match self.player1 {
+            Shape::Paper => match self.player2 {
+                Shape::Paper => RoundOutcome::Draw,
+                Shape::Rock => RoundOutcome::Won,
+                Shape::Scissors => RoundOutcome::Lost,
+            },
+            Shape::Rock => match self.player2 {
+                Shape::Paper => RoundOutcome::Lost,
+                Shape::Rock => RoundOutcome::Draw,
+                Shape::Scissors => RoundOutcome::Won,
+            },
+            Shape::Scissors => match self.player2 {
+                Shape::Paper => RoundOutcome::Won,
+                Shape::Rock => RoundOutcome::Lost,
+                Shape::Scissors => RoundOutcome::Draw,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RoundPuzzle {
+    player2: Shape,
+    expected_outcome: RoundOutcome,
+}
+
+impl RoundPuzzle {
+    fn new(player2: Shape, expected_outcome: RoundOutcome) -> Self {
+        Self {
+            player2,
+            expected_outcome,
+        }
+    }
+
+    // "A Y": the shape of the opponent, then how the round must end.
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let source = Source::new(line);
+        let (opponent, outcome) = source.split_once(line, " ")?;
+        Ok(RoundPuzzle::new(
+            Shape::new(&source, opponent)?,
+            RoundOutcome::new(&source, outcome)?,
+        ))
+    }
+
+    fn guess_round(&self) -> Round {
+        let player1: Shape = match self.expected_outcome {
+            RoundOutcome::Draw => self.player2,
+            RoundOutcome::Lost => match self.player2 {
+                Shape::Paper => Shape::Rock,
+                Shape::Rock => Shape::Scissors,
+                Shape::Scissors => Shape::Paper,
+            },
+            RoundOutcome::Won => match self.player2 {
+                Shape::Paper => Shape::Scissors,
+                Shape::Rock => Shape::Paper,
+                Shape::Scissors => Shape::Rock,
+            },
+        };
+        Round {
+            player1,
+            player2: self.player2,
+        }
+    }
+}
+
+// Every round must read as both a round of part1 and a round puzzle of part2.
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    process(
+        input,
+        |line: String| -> Result<(), Box<dyn Error>> {
+            Round::parse(&line)?;
+            RoundPuzzle::parse(&line)?;
+            Ok(())
+        },
+        |_| Ok(()),
+    )?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let mut score_part1: u64 = 0;
+    process(
+        input,
+        |line: String| -> Result<Round, Box<dyn Error>> { Ok(Round::parse(&line)?) },
+        |round: Round| {
+            score_part1 += round.score();
+            Ok(())
+        },
+    )?;
+    Ok(score_part1)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let mut score_part2: u64 = 0;
+    process(
+        input,
+        |line: String| -> Result<RoundPuzzle, Box<dyn Error>> { Ok(RoundPuzzle::parse(&line)?) },
+        |round_puzzle: RoundPuzzle| {
+            score_part2 += round_puzzle.guess_round().score();
+            Ok(())
+        },
+    )?;
+    Ok(score_part2)
+}
+
+#[cfg(test)]
+fn input_file() -> std::io::BufReader<std::fs::File> {
+    std::io::BufReader::new(std::fs::File::open("./input.prod").expect("input file should exist"))
+}
+
+#[test]
+fn calculate_score() {
+    assert_eq!(13484, part1(input_file()).unwrap())
+}
+
+#[test]
+fn guess_shape() {
+    assert_eq!(13433, part2(input_file()).unwrap())
+}
+
+#[test]
+fn parsers_never_panic() {
+    aoc_common::fuzz::parses_input("A Y\nB X\nC Z\n", |input| parse(input));
+}
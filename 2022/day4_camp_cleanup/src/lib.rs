@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use aoc_common::input::process;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::ranges::RangeSet;
+use std::error::Error;
+use std::io::BufRead;
+
+struct SectionRange {
+    sections: RangeSet,
+}
+
+impl SectionRange {
+    fn contains(&self, other: &SectionRange) -> bool {
+        self.sections.is_superset(&other.sections)
+    }
+
+    fn overlap(&self, other: &SectionRange) -> bool {
+        self.sections.overlaps(&other.sections)
+    }
+}
+
+impl FromStr for SectionRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (a, b) = source.split_once(s, "-")?;
+        let (a, b): (i64, i64) = (source.parse(a)?, source.parse(b)?);
+        if a > b {
+            return Err(source.error(s, "range must not be reversed"));
+        }
+        Ok(SectionRange {
+            sections: RangeSet::from(a..=b),
+        })
+    }
+}
+
+enum Event {
+    ElveSectionAssignment(SectionRange, SectionRange),
+}
+
+impl FromStr for Event {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (elve_1_sections, elve_2_sections) = source.split_once(s, ",")?;
+        Ok(Self::ElveSectionAssignment(
+            source.parse(elve_1_sections)?,
+            source.parse(elve_2_sections)?,
+        ))
+    }
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    let mut counter = 0;
+    process(
+        input,
+        |line: String| Ok(Event::from_str(&line)?),
+        |event: Event| match event {
+            Event::ElveSectionAssignment(section1, section2) => {
+                if section1.contains(&section2) || section2.contains(&section1) {
+                    counter += 1;
+                }
+                Ok(())
+            }
+        },
+    )?;
+    Ok(counter)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    let mut counter = 0;
+    process(
+        input,
+        |line: String| Ok(Event::from_str(&line)?),
+        |event: Event| match event {
+            Event::ElveSectionAssignment(section1, section2) => {
+                if section1.overlap(&section2) {
+                    counter += 1;
+                }
+                Ok(())
+            }
+        },
+    )?;
+    Ok(counter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<SectionRange>(&["2-4", "6-6", "10-200"]);
+        fuzz::parses::<Event>(&fuzz::lines(EXAMPLE));
+    }
+}
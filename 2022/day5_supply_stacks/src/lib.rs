@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::{error::Error, str::FromStr};
+
+use aoc_common::input::process;
+use aoc_common::parse::{ParseError, Source};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Operation {
+    count: i32,
+    from: usize,
+    to: usize,
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (s_move, s_from_to) = source.split_once(s, " from ")?;
+        let move_value = source.strip_prefix(s_move, "move ")?;
+        let (from_value, to_value) = source.split_once(s_from_to, " to ")?;
+        Ok(Self {
+            count: source.parse(move_value)?,
+            from: source.parse(from_value)?,
+            to: source.parse(to_value)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct StacksLevel {
+    stacks: Vec<char>,
+}
+
+impl FromStr for StacksLevel {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // each stack takes 4 columns: "[A] "
+        let source = Source::new(s);
+        let stacks_len = (s.len() + 1) / 4;
+        let mut stacks: Vec<char> = Vec::new();
+        for i in 0..stacks_len {
+            match s.get(i * 4..i * 4 + 3) {
+                Some("   ") => stacks.push(' '),
+                Some(item) if item.starts_with('[') && item.ends_with(']') => {
+                    stacks.push(item.chars().nth(1).unwrap())
+                }
+                _ => {
+                    let rest = s.get(i * 4..).unwrap_or(s);
+                    return Err(source.error(rest, "expected a crate like '[A]'"));
+                }
+            }
+        }
+        Ok(Self { stacks })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Init(StacksLevel),
+    Move(Operation),
+    LineNumber,
+    Empty,
+}
+
+impl FromStr for Event {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Event::Empty);
+        }
+        if s.starts_with(" 1") {
+            return Ok(Event::LineNumber);
+        }
+        if s.starts_with("move") {
+            return Ok(Event::Move(Operation::from_str(s)?));
+        }
+        Ok(Event::Init(StacksLevel::from_str(s)?))
+    }
+}
+
+struct StackCrates {
+    stacks: Vec<VecDeque<char>>,
+}
+
+impl StackCrates {
+    fn new() -> Self {
+        Self { stacks: vec![] }
+    }
+
+    fn init_level(&mut self, level: StacksLevel) {
+        if self.stacks.len() < level.stacks.len() {
+            self.stacks.resize(level.stacks.len(), VecDeque::new())
+        }
+        for (stack, item) in level.stacks.iter().enumerate() {
+            if *item != ' ' {
+                self.stacks[stack].push_back(*item)
+            }
+        }
+    }
+
+    fn move_operation(&mut self, operation: Operation) -> Result<(), String> {
+        let stack = |i: usize| {
+            i.checked_sub(1)
+                .filter(|&i| i < self.stacks.len())
+                .ok_or(format!("there is no stack {}", i))
+        };
+        let (from, to) = (stack(operation.from)?, stack(operation.to)?);
+        let mut count: i32 = operation.count;
+        let mut items = Vec::new();
+        while count > 0 {
+            items.push(
+                self.stacks[from]
+                    .pop_front()
+                    .ok_or(format!("stack {} is empty", operation.from))?,
+            );
+            count -= 1;
+        }
+        items.reverse();
+        for item in items.iter() {
+            self.stacks[to].push_front(*item);
+        }
+        Ok(())
+    }
+
+    fn get_top_items(&self) -> String {
+        let mut items = String::from("");
+        for stack in self.stacks.iter() {
+            if let Some(v) = stack.front() {
+                items.push(*v)
+            }
+        }
+        items
+    }
+}
+
+// The crane moves several crates at once, so the order of the moved crates is retained (part2).
+pub fn part2<R: BufRead>(input: R) -> Result<String, Box<dyn Error>> {
+    let mut stack_crates = StackCrates::new();
+
+    process(
+        input,
+        |line| Ok(Event::from_str(&line)?),
+        |event| {
+            match event {
+                Event::Init(stacks) => {
+                    stack_crates.init_level(stacks);
+                }
+                Event::Move(operation) => {
+                    stack_crates.move_operation(operation)?;
+                }
+                Event::Empty | Event::LineNumber => {}
+            }
+            Ok(())
+        },
+    )?;
+
+    Ok(stack_crates.get_top_items())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Event>(&fuzz::lines(EXAMPLE));
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::BufRead;
+
+const MARKER_SIZE_START_OF_PACKET: usize = 4;
+const MARKER_SIZE_START_OF_MESSAGE: usize = 14;
+
+// The last characters received on a line, with how often each of them occurs,
+// so that every new character is checked in constant time and memory however
+// long the datastream.
+struct Marker {
+    size: usize,
+    window: VecDeque<u8>,
+    counts: [usize; 256],
+    duplicates: usize,
+    received: usize,
+}
+
+impl Marker {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            window: VecDeque::with_capacity(size + 1),
+            counts: [0; 256],
+            duplicates: 0,
+            received: 0,
+        }
+    }
+
+    // Receives the next character, returning the number of characters
+    // received on the line once the last `size` of them are all different.
+    fn receive(&mut self, data: u8) -> Option<usize> {
+        if data == b'\n' {
+            // every line is a datastream of its own
+            *self = Self::new(self.size);
+            return None;
+        }
+        self.received += 1;
+        self.window.push_back(data);
+        self.counts[data as usize] += 1;
+        if self.counts[data as usize] == 2 {
+            self.duplicates += 1;
+        }
+        if self.window.len() > self.size {
+            let old = self.window.pop_front().unwrap() as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.duplicates -= 1;
+            }
+        }
+        (self.window.len() == self.size && self.duplicates == 0).then_some(self.received)
+    }
+}
+
+fn find_marker<R: BufRead>(mut input: R, marker_size: usize) -> Result<usize, Box<dyn Error>> {
+    let mut marker = Marker::new(marker_size);
+    loop {
+        let buffer = input.fill_buf()?;
+        if buffer.is_empty() {
+            return Err("marker not found".into());
+        }
+        if let Some(marker_i) = buffer.iter().find_map(|&data| marker.receive(data)) {
+            return Ok(marker_i);
+        }
+        let consumed = buffer.len();
+        input.consume(consumed);
+    }
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    find_marker(input, MARKER_SIZE_START_OF_PACKET)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    find_marker(input, MARKER_SIZE_START_OF_MESSAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::memory;
+    use std::io::{self, BufReader, Read};
+
+    aoc_common::count_allocations!();
+
+    #[test]
+    fn markers_are_found_on_each_line() {
+        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes()).unwrap(), 5);
+        assert_eq!(
+            part2("nppdvjthqldpwncqszvftbrmjlhg".as_bytes()).unwrap(),
+            23
+        );
+        // the first line has no marker, the count starts over on the second
+        assert_eq!(part1("aabbcc\nxxxabc\n".as_bytes()).unwrap(), 6);
+        assert!(part1("abca\nab".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn datastreams_are_read_in_constant_memory() {
+        // 16 MiB without a marker, then one
+        let datastream = io::repeat(b'a')
+            .take(16 << 20)
+            .chain("bcdefghijklmnop".as_bytes());
+        let (marker, usage) = memory::measure(|| part2(BufReader::new(datastream)));
+        assert_eq!(marker.unwrap(), (16 << 20) + 13);
+        assert!(usage.heap.unwrap().peak < 64 << 10);
+    }
+}
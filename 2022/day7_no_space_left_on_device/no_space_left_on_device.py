@@ -129,9 +129,6 @@ def main():
     x = get_min_folder_to_delete(ft.nodes["/"].size, ft.nodes["/"])
     print(f"delete_size: {x}")
 
-    # dirs_size: 1391690
-    # delete_size: 5469168
-
 
 if __name__ == "__main__":
     main()
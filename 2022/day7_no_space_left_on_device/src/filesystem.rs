@@ -1,3 +1,4 @@
+use aoc_common::trace::trace;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -31,7 +32,7 @@ impl TreeNode {
         Self {
             name,
             children: HashMap::new(),
-            size: size,
+            size,
             is_dir: false,
         }
     }
@@ -60,26 +61,17 @@ impl TreeNode {
             return Some(self);
         }
         match self.children.get_mut(&path[i]) {
-            Some(node) => {
-                return node.get_node_iter(path, i + 1);
-            }
-            _ => return None,
-        };
-    }
-
-    #[allow(dead_code)]
-    pub fn print(&self) {
-        self.print_iter(0);
+            Some(node) => node.get_node_iter(path, i + 1),
+            _ => None,
+        }
     }
 
-    #[allow(dead_code)]
-    fn print_iter(&self, i: usize) {
-        for _ in 0..i {
-            print!("    ");
-        }
-        println!("{} (size={})", self.name, self.size);
-        for (_, node) in self.children.iter() {
-            node.print_iter(i + 1);
+    // Logs the node and everything below it, one line per node, indented by
+    // depth.
+    pub fn trace(&self, depth: usize) {
+        trace!("{}{}", "    ".repeat(depth), self);
+        for node in self.children.values() {
+            node.trace(depth + 1);
         }
     }
 
@@ -118,16 +110,15 @@ impl TreeNode {
         }
         let mut current: Option<&TreeNode> = None;
         for (_, node) in self.children.iter() {
-            match node.get_min_dir_of_size_higher_than(min_size) {
-                Some(node_min_dir) => match current {
+            if let Some(node_min_dir) = node.get_min_dir_of_size_higher_than(min_size) {
+                match current {
                     Some(min_dir) => {
                         if node_min_dir.size < min_dir.size {
                             current = Some(node_min_dir);
                         }
                     }
                     None => current = Some(node_min_dir),
-                },
-                None => {}
+                }
             }
         }
         if current.is_some() {
@@ -151,46 +142,44 @@ impl Tree {
     pub fn new(total_space: u64) -> Self {
         let root = TreeNode::new_dir("/".to_string());
         Self {
-            root: root,
+            root,
             path: vec![],
             total_space,
         }
     }
 
-    pub fn cd(&mut self, dir: &String) {
+    pub fn cd(&mut self, dir: &String) -> Result<(), String> {
         if dir.eq("/") {
             self.path = vec![];
-            return;
+            return Ok(());
         }
         if dir.eq("..") {
             self.path.pop();
-            return;
+            return Ok(());
         }
-        let current = match self.current_node() {
-            Some(node) => node,
-            None => panic!("current node not found, path: {:?}", self.path),
-        };
+        let path = format!("{:?}", self.path);
+        let current = self
+            .current_node()
+            .ok_or_else(|| format!("current node not found, path: {}", path))?;
         match current.get_child(dir.to_string()) {
             Some(_) => self.path.push(dir.to_string()),
-            None => panic!("dir '{dir}' not found, current path: {:?}", self.path),
+            None => return Err(format!("dir '{dir}' not found, current path: {}", path)),
         }
+        Ok(())
     }
 
-    pub fn add_dir(&mut self, name: &String) {
-        match self.current_node() {
-            Some(node) => {
-                node.add_child(TreeNode::new_dir(name.to_string()));
-            }
-            None => panic!("current node not found, path: {:?}", self.path),
-        }
+    pub fn add_dir(&mut self, name: &String) -> Result<(), String> {
+        let path = format!("{:?}", self.path);
+        let node = self
+            .current_node()
+            .ok_or_else(|| format!("current node not found, path: {}", path))?;
+        node.add_child(TreeNode::new_dir(name.to_string()));
+        Ok(())
     }
 
     pub fn add_file(&mut self, name: &String, size: u64) {
-        match self.current_node() {
-            Some(node) => {
-                node.add_child(TreeNode::new_file(name.to_string(), size));
-            }
-            None => return,
+        if let Some(node) = self.current_node() {
+            node.add_child(TreeNode::new_file(name.to_string(), size));
         }
     }
 
@@ -0,0 +1,84 @@
+mod filesystem;
+mod parser;
+
+use parser::*;
+use std::error::Error;
+use std::io::BufRead;
+
+struct Shell {
+    pub fs: filesystem::Tree,
+}
+
+impl Shell {
+    fn new(fs_total_space: u64) -> Self {
+        Self {
+            fs: filesystem::Tree::new(fs_total_space),
+        }
+    }
+
+    fn simulate(&mut self, execution: &CommandExecution) -> Result<(), String> {
+        match &execution.command {
+            Command::LS() => {
+                for output_line in &execution.output {
+                    match output_line {
+                        CommandOutputLine::File(file) => {
+                            self.fs.add_file(&file.name, file.size);
+                        }
+                        CommandOutputLine::Dir(dir) => {
+                            self.fs.add_dir(&dir.name)?;
+                        }
+                    }
+                }
+            }
+            Command::CD(dir) => {
+                self.fs.cd(dir)?;
+                // discard the output, should be empty
+            }
+        }
+        Ok(())
+    }
+}
+
+fn load_shell<R: BufRead>(input: R) -> Result<Shell, Box<dyn Error>> {
+    let space_total = 70_000_000;
+    let mut shell = Shell::new(space_total);
+
+    let command_executions = load_input_command_executions(input)?;
+
+    for command_execution in command_executions.iter() {
+        shell.simulate(command_execution)?;
+    }
+
+    shell.fs.root.eval_size();
+    shell.fs.root.trace(0);
+
+    Ok(shell)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_shell(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let shell = load_shell(input)?;
+    Ok(shell.fs.sum_size_of_dirs_less_than(100_000))
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u64, Box<dyn Error>> {
+    let shell = load_shell(input)?;
+    Ok(shell.fs.get_size_of_dir_to_delete(30_000_000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
@@ -1,19 +1,9 @@
+use aoc_common::input::{lines, InputError};
+use aoc_common::parse::{ParseError, Source};
 use std::error::Error;
-use std::fs::File as FSFile;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ParseError {
-    message: String,
-}
-
-impl ParseError {
-    fn new(message: String) -> Self {
-        Self { message }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq)]
 pub struct File {
     pub name: String,
@@ -25,17 +15,12 @@ impl FromStr for File {
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         // "14848514 b.txt"
-        let (size, name) = line.split_once(" ").unwrap();
-        match size.parse() {
-            Ok(s) => Ok(Self {
-                size: s,
-                name: String::from(name),
-            }),
-            Err(_) => Err(ParseError::new(format!(
-                "size is not a number {}; line: \"{}\"",
-                size, line
-            ))),
-        }
+        let source = Source::new(line);
+        let (size, name) = source.split_once(line, " ")?;
+        Ok(Self {
+            size: source.parse(size)?,
+            name: String::from(name),
+        })
     }
 }
 
@@ -48,9 +33,10 @@ impl FromStr for Dir {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, name) = s.split_once(" ").unwrap();
+        let source = Source::new(s);
+        let (dir, name) = source.split_once(s, " ")?;
         if !dir.eq("dir") {
-            return Err(ParseError::new("not a directory".to_string()));
+            return Err(source.error(dir, "not a directory"));
         }
         Ok(Self {
             name: name.to_string(),
@@ -69,9 +55,9 @@ impl FromStr for CommandOutputLine {
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         if line.starts_with("dir") {
-            return Ok(CommandOutputLine::Dir(Dir::from_str(line)?));
+            Ok(CommandOutputLine::Dir(Dir::from_str(line)?))
         } else {
-            return Ok(CommandOutputLine::File(File::from_str(line)?));
+            Ok(CommandOutputLine::File(File::from_str(line)?))
         }
     }
 }
@@ -89,10 +75,10 @@ impl FromStr for Command {
         if s.starts_with("$ ls") {
             return Ok(Command::LS());
         }
-        if s.starts_with("$ cd ") {
-            return Ok(Command::CD(s.split_once(" cd ").unwrap().1.to_string()));
+        if let Some(dir) = s.strip_prefix("$ cd ") {
+            return Ok(Command::CD(dir.to_string()));
         }
-        Err(ParseError::new(format!("command not supported: {}", s)))
+        Err(Source::new(s).error(s, "command not supported"))
     }
 }
 
@@ -102,8 +88,8 @@ pub struct CommandExecution {
     pub output: Vec<CommandOutputLine>,
 }
 
-pub fn load_input_command_executions(
-    input_file: &str,
+pub fn load_input_command_executions<R: BufRead>(
+    input: R,
 ) -> Result<Vec<CommandExecution>, Box<dyn Error>> {
     let mut command_executions = Vec::new();
 
@@ -111,31 +97,40 @@ pub fn load_input_command_executions(
     let mut command_output: Vec<CommandOutputLine> = Vec::new();
     let mut command_read = false;
 
-    let file = FSFile::open(input_file).expect("input file should exist");
-    let file_buffer = BufReader::new(file);
-    for line_wrapped in file_buffer.lines() {
-        let line = line_wrapped.unwrap();
+    for line in lines(input) {
+        let (n, line) = line?;
         if line.starts_with("$ ") {
             if command_read {
                 command_executions.push(CommandExecution {
-                    command: command,
+                    command,
                     output: command_output,
                 });
             }
-            command = Command::from_str(&line).unwrap();
+            command = Command::from_str(&line).map_err(|e| InputError::parse(n, e))?;
             command_output = Vec::new();
             command_read = true;
         } else {
-            match CommandOutputLine::from_str(&line) {
-                Ok(output_line) => command_output.push(output_line),
-                Err(e) => panic!("error: {}", e.message),
-            }
+            let output_line =
+                CommandOutputLine::from_str(&line).map_err(|e| InputError::parse(n, e))?;
+            command_output.push(output_line);
         }
     }
     command_executions.push(CommandExecution {
-        command: command,
+        command,
         output: command_output,
     });
 
     Ok(command_executions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Command>(&["$ cd /", "$ cd a", "$ ls"]);
+        fuzz::parses::<CommandOutputLine>(&["dir a", "14848514 b.txt", "29116 f"]);
+    }
+}
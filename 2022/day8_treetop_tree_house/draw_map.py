@@ -1,20 +0,0 @@
-import numpy as np
-import matplotlib.pyplot as plt
-
-
-def do():
-    fig, ax = plt.subplots()
-
-    with open("./input.prod", "r") as f:
-        data = f.read()
-
-    numbers = [[int(v) for v in line] for line in data.split("\n")]
-    matrix = np.matrix(numbers)
-
-    ax.matshow(matrix, cmap=plt.cm.Greens)
-
-    plt.savefig("forest.png")
-
-
-if __name__ == "__main__":
-    do()
@@ -0,0 +1,275 @@
+use aoc_common::grid::Grid;
+use aoc_common::random::Rng;
+use aoc_common::render::{Cell, Frame, Recorder, Render};
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy)]
+struct MaxHeightPoint {
+    x: usize,
+    y: usize,
+}
+
+struct ViewingDistance {
+    up: MaxHeightPoint,
+    left: MaxHeightPoint,
+    right: MaxHeightPoint,
+    down: MaxHeightPoint,
+}
+
+impl ViewingDistance {
+    fn scenic_score(&self, tree_x: usize, tree_y: usize) -> u32 {
+        let up = tree_y as i32 - self.up.y as i32;
+        let left = tree_x as i32 - self.left.x as i32;
+        let right = self.right.x as i32 - tree_x as i32;
+        let down = tree_y as i32 - self.down.y as i32;
+
+        i32::abs(up * left * right * down) as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tree {
+    max_height_up: Option<u8>,
+    max_height_left: Option<u8>,
+    max_height_right: Option<u8>,
+    max_height_down: Option<u8>,
+
+    height: u8,
+}
+
+impl Tree {
+    fn new(height: u8) -> Self {
+        Self {
+            max_height_up: None,
+            max_height_left: None,
+            max_height_right: None,
+            max_height_down: None,
+            height,
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        vec![
+            self.max_height_down,
+            self.max_height_up,
+            self.max_height_left,
+            self.max_height_right,
+        ]
+        .into_iter()
+        .any(|i| match i {
+            Some(max_height) => max_height < self.height,
+            None => true,
+        })
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+struct Map {
+    trees: Grid<Tree>,
+}
+
+impl Map {
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.trees.width(), self.trees.height())
+    }
+
+    pub fn count_visible_trees(&self) -> u32 {
+        self.trees
+            .iter()
+            .filter(|(_, tree)| tree.is_visible())
+            .count() as u32
+    }
+
+    fn get(&self, x: usize, y: usize) -> &Tree {
+        &self.trees[(x, y)]
+    }
+
+    fn get_tree_viewing_distance(&self, x: usize, y: usize) -> ViewingDistance {
+        let tree = self.get(x, y);
+
+        // Walks from the tree towards the edge until a tree at least as high blocks the view.
+        let view = |delta| {
+            let mut last = (x, y);
+            for pos in self.trees.ray((x, y), delta) {
+                last = pos;
+                if self.trees[pos].height >= tree.height {
+                    break;
+                }
+            }
+            MaxHeightPoint {
+                x: last.0,
+                y: last.1,
+            }
+        };
+
+        ViewingDistance {
+            left: view((-1, 0)),
+            up: view((0, -1)),
+            down: view((0, 1)),
+            right: view((1, 0)),
+        }
+    }
+
+    pub fn find_best_scienic_score(&self) -> u32 {
+        let mut best_score = 0;
+        // iterate over trees and get viewing distance
+        let (width, height) = self.dimensions();
+        for y in 1..height.saturating_sub(1) {
+            for x in 1..width.saturating_sub(1) {
+                let scenic_score = self.get_tree_viewing_distance(x, y).scenic_score(x, y);
+                if scenic_score > best_score {
+                    best_score = scenic_score;
+                }
+            }
+        }
+
+        best_score
+    }
+
+    pub fn eval_trees_visibility(&mut self) {
+        let (width, height) = self.dimensions();
+
+        // eval max height up
+        for x in 0..width {
+            let mut max_height = 0;
+            let mut is_on_edge = true;
+            for y in 0..height {
+                let tree = &mut self.trees[(x, y)];
+                if !is_on_edge {
+                    tree.max_height_up = Some(max_height);
+                }
+                if tree.height > max_height {
+                    max_height = tree.height;
+                }
+                is_on_edge = false;
+            }
+        }
+        // eval max height down
+        for x in 0..width {
+            let mut max_height = 0;
+            let mut is_on_edge = true;
+            for yd in 0..height {
+                let y = height - yd - 1;
+                let tree = &mut self.trees[(x, y)];
+                if !is_on_edge {
+                    tree.max_height_down = Some(max_height);
+                }
+                if tree.height > max_height {
+                    max_height = tree.height;
+                }
+                is_on_edge = false;
+            }
+        }
+        // eval max height left
+        for y in 0..height {
+            let mut max_height = 0;
+            let mut is_on_edge = true;
+            for x in 0..width {
+                let tree = &mut self.trees[(x, y)];
+                if !is_on_edge {
+                    tree.max_height_left = Some(max_height);
+                }
+                if tree.height > max_height {
+                    max_height = tree.height;
+                }
+                is_on_edge = false;
+            }
+        }
+        // eval max height right
+        for y in 0..height {
+            let mut max_height = 0;
+            let mut is_on_edge = true;
+            for xd in 0..width {
+                let x = width - xd - 1;
+                let tree = &mut self.trees[(x, y)];
+                if !is_on_edge {
+                    tree.max_height_right = Some(max_height);
+                }
+                if tree.height > max_height {
+                    max_height = tree.height;
+                }
+                is_on_edge = false;
+            }
+        }
+    }
+}
+
+// The forest as a height map, higher trees in brighter green.
+impl Render for Map {
+    fn render(&self) -> Frame {
+        Frame::from_grid(&self.trees, |tree| {
+            Cell::from(char::from_digit(tree.height as u32, 10).unwrap_or('?'))
+        })
+    }
+}
+
+fn load_map<R: BufRead>(input: R) -> Result<Map, Box<dyn Error>> {
+    let trees = Grid::parse(input, |c| match c.to_digit(10) {
+        Some(height) => Ok(Tree::new(height as u8)),
+        None => Err(format!("invalid tree height '{}'", c)),
+    })?;
+    let mut map = Map { trees };
+
+    map.eval_trees_visibility();
+    Ok(map)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_map(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    Ok(load_map(input)?.count_visible_trees())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    Ok(load_map(input)?.find_best_scienic_score())
+}
+
+// A forest of `size` by `size` trees of random heights.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut forest = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        forest.extend((0..size).map(|_| char::from(b'0' + rng.below(10) as u8)));
+        forest.push('\n');
+    }
+    forest
+}
+
+// Records a picture of the forest.
+pub fn record<R: BufRead>(input: R, recorder: &mut Recorder) -> Result<(), Box<dyn Error>> {
+    recorder.record(&load_map(input)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for (seed, size) in [(0, 1), (1, 5), (2, 40)] {
+            let input = generate(seed, size);
+            // the trees on the edge are all visible
+            assert!(part1(input.as_bytes()).unwrap() as usize >= 4 * (size - 1));
+            part2(input.as_bytes()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::geometry::{Direction, Point2};
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::render::{Frame, Recorder, Render};
+use aoc_common::trace::trace;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Move {
+    direction: Direction,
+    length: i32,
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(line);
+        let (direction, length) = source.split_once(line, " ")?;
+        let mut chars = direction.chars();
+        let direction = match (chars.next(), chars.next()) {
+            (Some(c @ ('U' | 'D' | 'L' | 'R')), None) => Direction::try_from(c).unwrap(),
+            _ => return Err(source.error(direction, "expected one of U, D, L, R")),
+        };
+        Ok(Self {
+            direction,
+            length: source.parse(length)?,
+        })
+    }
+}
+
+struct Rope {
+    parts: Vec<Point2>,
+}
+
+impl Rope {
+    fn new(size: usize) -> Self {
+        Self {
+            parts: vec![Point2::ORIGIN; size],
+        }
+    }
+
+    pub fn drag_towards(&mut self, direction: Direction) {
+        self.parts[0] = self.parts[0].step(direction);
+
+        for i in 1..self.parts.len() {
+            let head = self.parts[i - 1];
+            let tail = &mut self.parts[i];
+
+            // the tail only moves once it is no longer touching the head, and
+            // then always one step (straight or diagonal) towards it
+            if head.chebyshev(*tail) > 1 {
+                *tail += (head - *tail).signum();
+            }
+        }
+    }
+
+    pub fn get(&self) -> &Vec<Point2> {
+        &self.parts
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+// The rope within the area its head moves in, along with the fields visited
+// by its tail so far.
+struct Scene<'a> {
+    rope: &'a Rope,
+    visited: &'a HashSet<Point2>,
+    top_left: Point2,
+    bottom_right: Point2,
+}
+
+impl Render for Scene<'_> {
+    fn render(&self) -> Frame {
+        let size = self.bottom_right - self.top_left;
+        let mut frame = Frame::new(size.x as usize + 1, size.y as usize + 1);
+        for &field in self.visited {
+            let at = field - self.top_left;
+            frame.draw(at.x, at.y, '#');
+        }
+        for (i, &part) in self.rope.parts.iter().enumerate().rev() {
+            let at = part - self.top_left;
+            let glyph = match i {
+                0 => 'H',
+                i => char::from_digit(i as u32 % 10, 10).unwrap(),
+            };
+            frame.draw(at.x, at.y, glyph);
+        }
+        frame
+    }
+}
+
+// The area covered by the head, the rest of the rope stays within it.
+fn head_bounds(moves: &[Move]) -> (Point2, Point2) {
+    let (mut top_left, mut bottom_right) = (Point2::ORIGIN, Point2::ORIGIN);
+    let mut head = Point2::ORIGIN;
+    for m in moves {
+        head += m.direction.delta() * m.length as i64;
+        top_left = Point2::new(top_left.x.min(head.x), top_left.y.min(head.y));
+        bottom_right = Point2::new(bottom_right.x.max(head.x), bottom_right.y.max(head.y));
+    }
+    (top_left, bottom_right)
+}
+
+fn count_positions_tail_visited<R: BufRead>(
+    input: R,
+    rope_size: usize,
+    recorder: &mut Recorder,
+) -> Result<usize, Box<dyn Error>> {
+    let moves: Vec<Move> = parse_lines(input)?;
+    let (top_left, bottom_right) = head_bounds(&moves);
+    let mut rope = Rope::new(rope_size);
+    let mut fields_visited_by_tail: HashSet<Point2> = HashSet::new();
+
+    for m in moves {
+        for _ in 0..m.length {
+            rope.drag_towards(m.direction);
+            trace!("{:?}: {}", m.direction, rope);
+
+            fields_visited_by_tail.insert(*rope.get().last().unwrap());
+            recorder.record(&Scene {
+                rope: &rope,
+                visited: &fields_visited_by_tail,
+                top_left,
+                bottom_right,
+            })?;
+        }
+    }
+
+    Ok(fields_visited_by_tail.len())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    count_positions_tail_visited(input, 2, &mut Recorder::none())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    count_positions_tail_visited(input, 10, &mut Recorder::none())
+}
+
+// Like `part2`, recording the rope after every step of its head.
+pub fn part2_recorded<R: BufRead>(
+    input: R,
+    recorder: &mut Recorder,
+) -> Result<usize, Box<dyn Error>> {
+    count_positions_tail_visited(input, 10, recorder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Move>(&fuzz::lines(EXAMPLE));
+    }
+}
@@ -0,0 +1,33 @@
+// Advent of Code {{year}} day {{day}}: {{title}}
+
+use aoc_common::input::lines;
+use std::error::Error;
+use std::io::BufRead;
+
+pub fn part1<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    let _lines = lines(input).collect::<Result<Vec<_>, _>>()?;
+    Err("part 1 is not solved yet".into())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    let _lines = lines(input).collect::<Result<Vec<_>, _>>()?;
+    Err("part 2 is not solved yet".into())
+}
+
+#[cfg(test)]
+fn input_file() -> std::io::BufReader<std::fs::File> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/input.dev");
+    std::io::BufReader::new(std::fs::File::open(path).expect("input file should exist"))
+}
+
+#[test]
+#[ignore = "not solved yet"]
+fn part1_dev() {
+    assert_eq!(0, part1(input_file()).unwrap());
+}
+
+#[test]
+#[ignore = "not solved yet"]
+fn part2_dev() {
+    assert_eq!(0, part2(input_file()).unwrap());
+}
@@ -1,9 +1,20 @@
-use std::fs::File as FSFile;
-use std::io::{BufRead, BufReader};
+use aoc_common::input::InputSource;
+use aoc_common::memory;
+use aoc_common::trace;
+use std::error::Error;
 
-fn main() {
-    let file = FSFile::open("./input.dev").expect("input file should exist");
-    for line_wrapped in BufReader::new(file).lines() {
-        println!("{}", line_wrapped.unwrap());
-    }
+aoc_common::count_allocations!();
+
+fn main() -> Result<(), Box<dyn Error>> {
+    trace::init(None)?;
+    let input = InputSource::from_args(env!("CARGO_MANIFEST_DIR"), "dev");
+    println!(
+        "{}",
+        memory::profile("part 1", || day_template::part1(input.open()?))?
+    );
+    println!(
+        "{}",
+        memory::profile("part 2", || day_template::part2(input.open()?))?
+    );
+    Ok(())
 }
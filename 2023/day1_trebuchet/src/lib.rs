@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::io::BufRead;
+
+fn part1_line_num(line: &str) -> u32 {
+    let char_to_digit = |c: char| c.to_digit(10);
+
+    let a: u32 = line.chars().find_map(&char_to_digit).unwrap_or(0);
+    let b: u32 = line.chars().rev().find_map(char_to_digit).unwrap_or(0);
+    10 * a + b
+}
+
+const DIGITS_STR: [(&str, u32); 10] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("zero", 0),
+];
+
+fn part2_str_digit(line: &str, i: usize) -> Option<u32> {
+    if let Some(c) = line.get(i..).unwrap().chars().next() {
+        if let Some(v) = c.to_digit(10) {
+            return Some(v);
+        }
+    }
+    let substr = line.get(i..).unwrap_or("");
+    for digit_str in DIGITS_STR {
+        if substr.starts_with(digit_str.0) {
+            return Some(digit_str.1);
+        }
+    }
+    None
+}
+
+fn part2_line_num(line: &str) -> u32 {
+    let a: u32 = line
+        .chars()
+        .enumerate()
+        .find_map(|(i, _)| part2_str_digit(line, i))
+        .unwrap_or(0);
+    let b: u32 = line
+        .chars()
+        .enumerate()
+        .find_map(|(i, _)| part2_str_digit(line, line.len() - i - 1))
+        .unwrap_or(0);
+    10 * a + b
+}
+
+fn sum_line_nums<R: BufRead>(input: R, line_num: fn(&str) -> u32) -> Result<u32, Box<dyn Error>> {
+    let mut sum = 0;
+    for line in input.lines() {
+        match line {
+            Ok(line) => sum += line_num(&line),
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
+    Ok(sum)
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    sum_line_nums(input, part1_line_num)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    sum_line_nums(input, part2_line_num)
+}
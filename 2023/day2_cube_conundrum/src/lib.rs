@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+
+#[derive(Debug, PartialEq, Eq)]
+struct CubeSet {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl FromStr for CubeSet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let mut cube_set = CubeSet {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for x in s.split(',') {
+            let (n, color) = source.split_once(x.trim(), " ")?;
+            let n: u32 = source.parse(n)?;
+            match color.trim() {
+                "red" => cube_set.red = n,
+                "green" => cube_set.green = n,
+                "blue" => cube_set.blue = n,
+                _ => return Err(source.error(color, "invalid cube color")),
+            }
+        }
+
+        Ok(cube_set)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    id: i32,
+    cube_sets: Vec<CubeSet>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(s);
+        let (game, cube_sets_raw) = source.split_once(s, ":")?;
+        let game_id: i32 = source.parse(source.strip_prefix(game, "Game ")?)?;
+
+        let mut cube_sets: Vec<CubeSet> = Vec::new();
+        for c in cube_sets_raw.split(';') {
+            cube_sets.push(source.parse(c.trim())?);
+        }
+
+        Ok(Self {
+            id: game_id,
+            cube_sets,
+        })
+    }
+}
+
+// Prints the game the way the input describes it, with all three colors.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, cube_set) in self.cube_sets.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ";" };
+            write!(
+                f,
+                "{} {} red, {} green, {} blue",
+                separator, cube_set.red, cube_set.green, cube_set.blue
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn part1_sum_game_ids_of_possible_games(games: &[Game], cube_set_max: CubeSet) -> i32 {
+    let games_possible = games.iter().filter(|game| {
+        for cube_set in game.cube_sets.iter() {
+            if cube_set.blue > cube_set_max.blue
+                || cube_set.red > cube_set_max.red
+                || cube_set.green > cube_set_max.green
+            {
+                return false;
+            }
+        }
+        true
+    });
+    games_possible.into_iter().map(|game| game.id).sum()
+}
+
+fn part2_sum_powers_of_minimal_sets(games: &[Game]) -> u32 {
+    let mut result = 0;
+    for game in games.iter() {
+        let max_red = game.cube_sets.iter().map(|cs| cs.red).max().unwrap_or(0);
+        let max_blue = game.cube_sets.iter().map(|cs| cs.blue).max().unwrap_or(0);
+        let max_green = game.cube_sets.iter().map(|cs| cs.green).max().unwrap_or(0);
+        result += max_red * max_green * max_blue;
+    }
+    result
+}
+
+fn load_games<R: BufRead>(input: R) -> Result<Vec<Game>, Box<dyn Error>> {
+    Ok(parse_lines(input)?)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_games(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i32, Box<dyn Error>> {
+    let games = load_games(input)?;
+    Ok(part1_sum_game_ids_of_possible_games(
+        &games,
+        CubeSet {
+            red: 12,
+            green: 13,
+            blue: 14,
+        },
+    ))
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    let games = load_games(input)?;
+    Ok(part2_sum_powers_of_minimal_sets(&games))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<CubeSet>(&["3 blue, 4 red", "1 red, 2 green, 6 blue", "2 green"]);
+        fuzz::round_trips::<Game>(&fuzz::lines(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
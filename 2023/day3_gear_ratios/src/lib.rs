@@ -0,0 +1,204 @@
+use aoc_common::input::{lines, InputError};
+use std::error::Error;
+use std::io::BufRead;
+
+// Sums of numbers from inputs of any size.
+type Sum = u64;
+
+#[derive(Debug)]
+struct Number {
+    column_start: usize,
+    column_end: usize,
+    value: u32,
+}
+
+// A row of the schematic with the numbers found on it. Numbers and gears
+// only reach the rows next to them, so the schematic is read three rows at a
+// time rather than all at once.
+#[derive(Debug, Default)]
+struct Row {
+    cells: Vec<char>,
+    numbers: Vec<Number>,
+}
+
+impl Row {
+    fn new(line: &str) -> Self {
+        let cells: Vec<char> = line.chars().collect();
+        let numbers = schematic_row_find_numbers(&cells);
+        Self { cells, numbers }
+    }
+
+    fn is_symbol(&self, column: usize) -> bool {
+        self.cells
+            .get(column)
+            .is_some_and(|&c| !c.is_ascii_digit() && c != '.')
+    }
+
+    fn gears(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cells.len()).filter(|&column| self.cells[column] == '*')
+    }
+}
+
+fn schematic_row_find_numbers(line: &[char]) -> Vec<Number> {
+    let mut numbers: Vec<Number> = Vec::new();
+    let mut number_start: Option<usize> = None;
+    for (j, c) in line.iter().enumerate() {
+        let c_is_digit = c.is_ascii_digit();
+        if c_is_digit && number_start.is_none() {
+            number_start = Some(j);
+        }
+        let c_is_last_char = j == line.len() - 1;
+        if c_is_last_char || !c_is_digit {
+            if let Some(ns) = number_start {
+                let mut ne = j;
+                if c_is_digit {
+                    ne += 1;
+                }
+                numbers.push(Number {
+                    column_start: ns,
+                    column_end: ne,
+                    value: line[ns..ne].iter().collect::<String>().parse().unwrap_or(0),
+                });
+            }
+            number_start = None;
+        }
+    }
+    numbers
+}
+
+// Calls `visit` with every row of the schematic, between the rows above and
+// below it (empty ones on the edges).
+fn schematic_scan<R, F>(input: R, mut visit: F) -> Result<(), InputError>
+where
+    R: BufRead,
+    F: FnMut([&Row; 3]),
+{
+    let mut above = Row::default();
+    let mut row: Option<Row> = None;
+    for line in lines(input) {
+        let below = Row::new(&line?.1);
+        if let Some(row) = row.take() {
+            visit([&above, &row, &below]);
+            above = row;
+        }
+        row = Some(below);
+    }
+    if let Some(row) = row {
+        visit([&above, &row, &Row::default()]);
+    }
+    Ok(())
+}
+
+fn part1_schematic_number_is_part(rows: [&Row; 3], number: &Number) -> bool {
+    (number.column_start.saturating_sub(1)..=number.column_end)
+        .any(|column| rows.iter().any(|row| row.is_symbol(column)))
+}
+
+fn part2_schematic_gear_find_adjacent_numbers(rows: [&Row; 3], gear: usize) -> Vec<&Number> {
+    rows.iter()
+        .flat_map(|row| row.numbers.iter())
+        .filter(|num| num.column_start <= gear + 1 && gear <= num.column_end)
+        .collect()
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<Sum, Box<dyn Error>> {
+    let mut part1_sum: Sum = 0;
+    schematic_scan(input, |rows| {
+        part1_sum += rows[1]
+            .numbers
+            .iter()
+            .filter(|number| part1_schematic_number_is_part(rows, number))
+            .map(|number| number.value as Sum)
+            .sum::<Sum>();
+    })?;
+
+    Ok(part1_sum)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<Sum, Box<dyn Error>> {
+    let mut part2_sum: Sum = 0;
+    schematic_scan(input, |rows| {
+        part2_sum += rows[1]
+            .gears()
+            .map(|g| part2_schematic_gear_find_adjacent_numbers(rows, g))
+            .filter(|ns| ns.len() == 2)
+            .map(|ns| ns[0].value as Sum * ns[1].value as Sum)
+            .sum::<Sum>();
+    })?;
+
+    Ok(part2_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::memory;
+    use std::io::{self, BufReader, Read};
+
+    aoc_common::count_allocations!();
+
+    const SCHEMATIC: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    // The schematic over and over, without ever holding it all in memory.
+    struct Repeat {
+        left: usize,
+        at: usize,
+    }
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.left == 0 {
+                return Ok(0);
+            }
+            let rest = &SCHEMATIC.as_bytes()[self.at..];
+            let n = rest.len().min(buf.len());
+            buf[..n].copy_from_slice(&rest[..n]);
+            self.at += n;
+            if self.at == SCHEMATIC.len() {
+                self.at = 0;
+                self.left -= 1;
+            }
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn edges_of_the_schematic() {
+        assert_eq!(part1(SCHEMATIC.as_bytes()).unwrap(), 4361);
+        assert_eq!(part2(SCHEMATIC.as_bytes()).unwrap(), 467835);
+        // no trailing newline, numbers on the first and last rows and columns
+        assert_eq!(part1("12.\n..*\n.34".as_bytes()).unwrap(), 46);
+        assert_eq!(part2("12.\n..*\n.34".as_bytes()).unwrap(), 408);
+        assert_eq!(part1("".as_bytes()).unwrap(), 0);
+    }
+
+    #[test]
+    fn schematics_are_read_in_constant_memory() {
+        let copies = 20_000;
+        let (sums, usage) = memory::measure(|| {
+            let sum1 = part1(BufReader::new(Repeat {
+                left: copies,
+                at: 0,
+            }))
+            .unwrap();
+            let sum2 = part2(BufReader::new(Repeat {
+                left: copies,
+                at: 0,
+            }))
+            .unwrap();
+            (sum1, sum2)
+        });
+        assert_eq!(sums, (4361 * copies as Sum, 467835 * copies as Sum));
+        assert!(usage.heap.unwrap().peak < 64 << 10);
+    }
+}
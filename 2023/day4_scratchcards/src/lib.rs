@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc_common::input::parse_lines;
+use aoc_common::parse::{ParseError, Source};
+
+fn power_of_two(e: u32) -> u32 {
+    let mut v = 1;
+    for _ in 0..e {
+        v *= 2;
+    }
+    v
+}
+
+#[derive(Debug)]
+struct Card {
+    numbers_mine: HashSet<u32>,
+    numbers_winning: HashSet<u32>,
+}
+
+impl Card {
+    fn count_matching_numbers(&self) -> usize {
+        let matching_numbers: HashSet<_> = self
+            .numbers_mine
+            .intersection(&self.numbers_winning)
+            .collect();
+        matching_numbers.len()
+    }
+
+    fn worth(&self) -> u32 {
+        let count = self.count_matching_numbers();
+        if count == 0 {
+            return 0;
+        }
+        power_of_two((count - 1) as u32)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        // "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+        let source = Source::new(line);
+        let numbers = |numbers_str: &str| -> Result<HashSet<u32>, ParseError> {
+            numbers_str
+                .split(" ")
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| source.parse(s.trim()))
+                .collect()
+        };
+
+        let (_, all_numbers) = source.split_once(line, ":")?;
+        let (numbers_mine_str, numbers_winning_str) = source.split_once(all_numbers, "|")?;
+
+        Ok(Card {
+            numbers_mine: numbers(numbers_mine_str)?,
+            numbers_winning: numbers(numbers_winning_str)?,
+        })
+    }
+}
+
+fn load_cards<R: BufRead>(input: R) -> Result<Vec<Card>, Box<dyn Error>> {
+    Ok(parse_lines(input)?)
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_cards(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    let cards = load_cards(input)?;
+    Ok(cards.iter().map(|c| c.worth()).sum())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<u32, Box<dyn Error>> {
+    let cards = load_cards(input)?;
+
+    let mut copies: Vec<u32> = vec![1; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let base = *copies.get(i).unwrap();
+        for j in 0..card.count_matching_numbers() {
+            let k = i + j + 1;
+            if let Some(c) = copies.get_mut(k) {
+                *c += base;
+            }
+        }
+    }
+    Ok(copies.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<Card>(&fuzz::lines(EXAMPLE));
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
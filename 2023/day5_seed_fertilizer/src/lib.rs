@@ -0,0 +1,218 @@
+use aoc_common::input::{records, InputError};
+use aoc_common::parse::{ParseError, Source};
+use aoc_common::ranges::{OffsetMap, RangeSet};
+use std::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug)]
+struct RangeMap {
+    destination_range_start: i64,
+    source_range_start: i64,
+    range_length: i64,
+}
+
+impl FromStr for RangeMap {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let source = Source::new(line);
+        let parts: Vec<&str> = line.split(" ").collect();
+
+        if parts.len() != 3 {
+            return Err(source.error(line, "expected three numbers"));
+        }
+
+        let destination_range_start: i64 = source.parse(parts[0].trim())?;
+        let source_range_start: i64 = source.parse(parts[1].trim())?;
+        let range_length: i64 = source.parse(parts[2].trim())?;
+        if destination_range_start < 0 || source_range_start < 0 || range_length < 0 {
+            return Err(source.error(line, "numbers must not be negative"));
+        }
+
+        Ok(Self {
+            destination_range_start,
+            source_range_start,
+            range_length,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct RangeMaps {
+    maps: OffsetMap,
+}
+
+impl RangeMaps {
+    fn new(maps: Vec<RangeMap>) -> Self {
+        let mut offsets = OffsetMap::new();
+        for map in maps {
+            offsets.insert(
+                map.source_range_start..map.source_range_start + map.range_length,
+                map.destination_range_start - map.source_range_start,
+            );
+        }
+        Self { maps: offsets }
+    }
+
+    fn convert(&self, l: i64) -> i64 {
+        self.maps.map(l)
+    }
+
+    fn convert_ranges(&self, ranges: &RangeSet) -> RangeSet {
+        self.maps.map_set(ranges)
+    }
+}
+
+// Almanac (also spelled almanack and almanach) is a regularly published listingof
+// of a set of current information about one or multiple subjects.
+// Source: Wikipedia
+#[derive(Debug)]
+struct Almanac {
+    seeds: Vec<i64>,
+    // line of the seeds, for the errors of part2
+    seeds_line: usize,
+    seed_to_soil: RangeMaps,
+    soil_to_fertilizer: RangeMaps,
+    fertilizer_to_water: RangeMaps,
+    water_to_light: RangeMaps,
+    light_to_temperature: RangeMaps,
+    temperature_to_humidity: RangeMaps,
+    humidity_to_location: RangeMaps,
+}
+
+impl Almanac {
+    fn maps(&self) -> [&RangeMaps; 7] {
+        [
+            &self.seed_to_soil,
+            &self.soil_to_fertilizer,
+            &self.fertilizer_to_water,
+            &self.water_to_light,
+            &self.light_to_temperature,
+            &self.temperature_to_humidity,
+            &self.humidity_to_location,
+        ]
+    }
+
+    fn seed_location(&self, seed: i64) -> i64 {
+        self.maps().iter().fold(seed, |x, map| map.convert(x))
+    }
+
+    // In part2 the seeds line lists pairs of range start and length.
+    fn seed_ranges(&self) -> Result<RangeSet, InputError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            let message = format!(
+                "{} seed numbers do not make pairs of range start and length",
+                self.seeds.len()
+            );
+            return Err(InputError::parse(self.seeds_line, ParseError::new(message)));
+        }
+        Ok(self
+            .seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect())
+    }
+
+    // All locations of the seeds in `seeds`, mapped range by range.
+    fn seed_locations(&self, seeds: &RangeSet) -> RangeSet {
+        self.maps()
+            .iter()
+            .fold(seeds.clone(), |x, map| map.convert_ranges(&x))
+    }
+}
+
+// Every section of the almanac is a blank-line separated record: the seeds line
+// first, followed by the maps in the order they are applied.
+fn load_almanac<R: BufRead>(input: R) -> Result<Almanac, Box<dyn Error>> {
+    let mut sections = records(input);
+
+    let seeds_record = sections.next().ok_or("missing seeds section")??;
+    let seeds_text = seeds_record.text();
+    let source = Source::new(&seeds_text);
+    let seeds: Vec<i64> = source
+        .strip_prefix(&seeds_text, "seeds: ")
+        .and_then(|numbers| numbers.split(" ").map(|s| source.parse(s.trim())).collect())
+        .map_err(|e| InputError::parse(seeds_record.line, e))?;
+
+    let mut maps: Vec<RangeMaps> = Vec::new();
+    for section in sections {
+        let section = section?;
+        // The first line is the map's header, e.g. "seed-to-soil map:".
+        let maps_section: Vec<RangeMap> = section
+            .numbered_lines()
+            .skip(1)
+            .map(|(line, map)| map.parse().map_err(|e| InputError::parse(line, e)))
+            .collect::<Result<_, _>>()?;
+        maps.push(RangeMaps::new(maps_section));
+    }
+
+    let [seed_to_soil, soil_to_fertilizer, fertilizer_to_water, water_to_light, light_to_temperature, temperature_to_humidity, humidity_to_location]: [RangeMaps; 7] =
+        maps.try_into()
+            .map_err(|_| "almanac must contain exactly 7 maps")?;
+
+    Ok(Almanac {
+        seeds,
+        seeds_line: seeds_record.line,
+        seed_to_soil,
+        soil_to_fertilizer,
+        fertilizer_to_water,
+        water_to_light,
+        light_to_temperature,
+        temperature_to_humidity,
+        humidity_to_location,
+    })
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    load_almanac(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let almanac = load_almanac(input)?;
+
+    // In part1 every number on the seeds line is a seed on its own.
+    let locations: Vec<i64> = almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.seed_location(seed))
+        .collect();
+
+    Ok(locations.iter().cloned().min().unwrap_or(0))
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<i64, Box<dyn Error>> {
+    let almanac = load_almanac(input)?;
+
+    // Instead of following every seed, whole ranges are mapped at once: each
+    // map only splits them where its own ranges begin and end.
+    let seeds = almanac.seed_ranges()?;
+
+    almanac
+        .seed_locations(&seeds)
+        .min()
+        .ok_or_else(|| "no seeds".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses::<RangeMap>(&["50 98 2", "52 50 48", "0 15 37"]);
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+
+    #[test]
+    fn odd_seed_counts_are_pairs_for_part2_only() {
+        let input = EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55 13 82");
+        assert_eq!(part1(input.as_bytes()).unwrap(), 35);
+        let error = part2(input.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("5 seed numbers"), "{}", error);
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Error};
+use aoc_common::input::{lines, InputError};
+use aoc_common::parse::{ParseError, Source};
+use std::io::BufRead;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Eq)]
+enum Instruction {
+    ADV,
+    BXL,
+    BST,
+    JNZ,
+    BXC,
+    OUT,
+    BDV,
+    CDV,
+}
+
+impl TryFrom<u64> for Instruction {
+    type Error = &'static str;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Instruction::ADV),
+            1 => Ok(Instruction::BXL),
+            2 => Ok(Instruction::BST),
+            3 => Ok(Instruction::JNZ),
+            4 => Ok(Instruction::BXC),
+            5 => Ok(Instruction::OUT),
+            6 => Ok(Instruction::BDV),
+            7 => Ok(Instruction::CDV),
+            _ => Err("Invalid instruction value"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Registers {
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+#[derive(Debug)]
+struct Device {
+    registers: Registers,
+    stack: Vec<u64>,
+    esp: usize,
+}
+
+impl Device {
+    fn _operand_combo(&self, operand: u64) -> u64 {
+        if operand <= 3 {
+            return operand;
+        }
+        match operand {
+            4 => self.registers.a,
+            5 => self.registers.b,
+            6 => self.registers.c,
+            _ => panic!("invalid operand combo: {}", operand),
+        }
+    }
+
+    // Register A divided by 2 to the power of the combo operand, which is a
+    // shift. Shifting by the width of the register or more leaves nothing.
+    fn _divide_a(&self, operand: u64) -> u64 {
+        u32::try_from(self._operand_combo(operand))
+            .ok()
+            .and_then(|shift| self.registers.a.checked_shr(shift))
+            .unwrap_or(0)
+    }
+
+    fn run(&mut self) -> Result<Vec<u8>, Error> {
+        let mut results: Vec<u8> = vec![];
+        loop {
+            if self.esp >= self.stack.len() {
+                break;
+            }
+            let instruction = Instruction::try_from(self.stack[self.esp])
+                .map_err(|e| anyhow!("failed to parse instruction: {}", e))?;
+
+            let operand = self.stack[self.esp + 1_usize];
+
+            let mut esp_inc = true;
+            match instruction {
+                Instruction::ADV => {
+                    // performs division. The numerator is the value in the A registers
+                    // the denominator is found by raising 2 to the power of the instruction's combo operand
+                    self.registers.a = self._divide_a(operand);
+                }
+                Instruction::BXL => {
+                    // calculates the bitwise XOR of register B and the instruction's literal operand, then stores the result in register B
+                    self.registers.b ^= operand;
+                }
+                Instruction::BST => {
+                    // instruction (opcode 2) calculates the value of its combo operand modulo 8 (thereby keeping only its lowest 3 bits),
+                    // then writes that value to the B register
+                    self.registers.b = self._operand_combo(operand) % 8;
+                }
+                Instruction::JNZ => {
+                    // instruction (opcode 3) does nothing if the A register is 0. However, if the A register is not zero,
+                    // it jumps by setting the instruction pointer to the value of its literal operand;
+                    // if this instruction jumps, the instruction pointer is not increased by 2 after this instruction
+                    if self.registers.a != 0 {
+                        esp_inc = false;
+                        self.esp = operand as usize;
+                    }
+                }
+                Instruction::BXC => {
+                    // instruction (opcode 4) calculates the bitwise XOR of register B and register C,
+                    // then stores the result in register B
+                    self.registers.b ^= self.registers.c;
+                }
+                Instruction::OUT => {
+                    // instruction (opcode 5) calculates the value of its combo operand modulo 8,
+                    // then outputs that value
+                    let result = self._operand_combo(operand) % 8;
+                    results.push(result as u8);
+                }
+                Instruction::BDV => {
+                    // instruction (opcode 6) works exactly like the adv instruction except that the result is stored in the B register
+                    self.registers.b = self._divide_a(operand);
+                }
+                Instruction::CDV => {
+                    // instruction (opcode 7) works exactly like the adv instruction except that the result is stored in the C register.
+                    self.registers.c = self._divide_a(operand);
+                }
+            }
+
+            if esp_inc {
+                self.esp += 2;
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+fn parse_device_definition<R: BufRead>(reader: R) -> Result<Device, anyhow::Error> {
+    let mut register_a = 0;
+    let mut register_b = 0;
+    let mut register_c = 0;
+    let mut stack: Vec<u64> = Vec::new();
+
+    for line in lines(reader) {
+        let (n, line) = line.map_err(|e| anyhow!("{}", e))?;
+        let source = Source::new(&line);
+        let register = || -> Result<u64, ParseError> {
+            let (_, value) = source.split_once(&line, ":")?;
+            source.parse(value.trim())
+        };
+        let parsed = if line.starts_with("Register A:") {
+            register().map(|v| register_a = v)
+        } else if line.starts_with("Register B:") {
+            register().map(|v| register_b = v)
+        } else if line.starts_with("Register C:") {
+            register().map(|v| register_c = v)
+        } else if line.starts_with("Program:") {
+            source.split_once(&line, ":").and_then(|(_, stack_str)| {
+                stack = stack_str
+                    .split(',')
+                    .map(|s| source.parse(s.trim()))
+                    .collect::<Result<_, _>>()?;
+                Ok(())
+            })
+        } else {
+            Ok(())
+        };
+        // InputError isn't Send, so it is turned into a message for anyhow
+        parsed.map_err(|e| anyhow!("{}", InputError::parse(n, e)))?;
+    }
+
+    Ok(Device {
+        registers: Registers {
+            a: register_a,
+            b: register_b,
+            c: register_c,
+        },
+        stack,
+        esp: 0,
+    })
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Error> {
+    parse_device_definition(input)?;
+    Ok(())
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<String, Error> {
+    let mut device = parse_device_definition(input)?;
+    let results = device.run()?;
+
+    let result = results
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+
+    #[test]
+    fn registers_do_not_overflow() {
+        let run = |a: &str, program: &str| {
+            let input = format!(
+                "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                a, program
+            );
+            part1(input.as_bytes()).unwrap()
+        };
+        // A is shifted by itself, far beyond its width.
+        assert_eq!(run("100", "0,4,5,4"), "0");
+        assert_eq!(run("10000000007", "5,4,0,1,5,4"), "7,3");
+    }
+}
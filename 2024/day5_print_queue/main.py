@@ -97,7 +97,7 @@ def part1(file_name: str):
         if queue.page_order.is_update_correct(update):
             result += update.middle_page()
 
-    print(result)  # dev: 143, prod: 6498
+    print(result)
 
 
 def part2(file_name: str):
@@ -109,7 +109,7 @@ def part2(file_name: str):
             fixed_update = queue.page_order.fix_update(update)
             result += fixed_update.middle_page()
 
-    print(result)  # dev: 123, prod: 5017
+    print(result)
 
 
 def main():
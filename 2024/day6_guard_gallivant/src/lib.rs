@@ -0,0 +1,158 @@
+use aoc_common::cycle::find_cycle;
+use aoc_common::geometry::{Direction, Point2};
+use aoc_common::grid::Grid;
+use aoc_common::input::InputError;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::BufRead;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct PointDirection {
+    point: Point2,
+    direction: Direction,
+}
+
+impl PointDirection {
+    /// Creates a new PointDirection.
+    fn new(point: Point2, direction: Direction) -> Self {
+        Self { point, direction }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    fields: Grid<char>,
+}
+
+impl Map {
+    /// Creates a new Map by reading the whole input, which only holds free
+    /// fields ('.'), obstructions ('#') and the guard ('^').
+    fn new_from_reader<R: BufRead>(reader: R) -> Result<Self, InputError> {
+        let fields = Grid::parse(reader, |c| match c {
+            '.' | '#' | '^' => Ok(c),
+            _ => Err(format!("unknown map field '{}'", c)),
+        })?;
+        Ok(Self { fields })
+    }
+
+    /// Finds all points containing the specified character.
+    fn find_field(&self, c: char) -> Vec<Point2> {
+        self.fields.find_all(&c).map(Point2::from).collect()
+    }
+
+    /// Retrieves the character at the given point, if within bounds.
+    fn get_field(&self, point: Point2) -> Option<char> {
+        let pos = self.fields.point_pos(point)?;
+        Some(self.fields[pos])
+    }
+
+    /// Sets the character at the given point, if within bounds.
+    fn set_field(&mut self, point: Point2, value: char) -> Option<()> {
+        let pos = self.fields.point_pos(point)?;
+        self.fields.set(pos, value).then_some(())
+    }
+}
+
+enum GuardPathingResult {
+    Cycle,
+    OutOfMap,
+}
+
+fn guard_pathing(map: &Map, point_start: Point2, direction_strt: Direction) -> GuardPathingResult {
+    let mut guard = PointDirection::new(point_start, direction_strt);
+    let step = |guard: &mut PointDirection| {
+        let point_next = guard.point.step(guard.direction);
+
+        match map.get_field(point_next) {
+            Some('#') => {
+                guard.direction = guard.direction.turn_right();
+            }
+            Some(_) => {
+                guard.point = point_next;
+            }
+            None => return false,
+        }
+        true
+    };
+
+    match find_cycle(&mut guard, usize::MAX, step, |guard| *guard) {
+        Some(_) => GuardPathingResult::Cycle,
+        None => GuardPathingResult::OutOfMap,
+    }
+}
+
+fn count_obstruction_points(map: &Map, start: Point2) -> usize {
+    let mut point_current = start;
+    let mut direction_current = Direction::Up;
+    let mut visited = HashSet::new();
+    let mut obstruction_points = HashSet::new();
+
+    let map = map.clone(); // Clone once to allow modifications.
+
+    loop {
+        let next_point = point_current.step(direction_current);
+
+        if let Some('.') = map.get_field(next_point) {
+            let already_visited = Direction::ALL
+                .iter()
+                .any(|&d| visited.contains(&PointDirection::new(next_point, d)));
+
+            if !already_visited {
+                let mut modified_map = map.clone();
+                modified_map.set_field(next_point, '#');
+
+                if let GuardPathingResult::Cycle =
+                    guard_pathing(&modified_map, point_current, direction_current)
+                {
+                    obstruction_points.insert(next_point);
+                }
+            }
+        }
+
+        match map.get_field(next_point) {
+            Some('#') => {
+                direction_current = direction_current.turn_right();
+            }
+            Some(_) => {
+                visited.insert(PointDirection::new(point_current, direction_current));
+                point_current = next_point;
+            }
+            None => break,
+        }
+    }
+
+    obstruction_points.len()
+}
+
+pub fn parse<R: BufRead>(input: R) -> Result<(), Box<dyn Error>> {
+    Map::new_from_reader(input)?;
+    Ok(())
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<usize, Box<dyn Error>> {
+    let map = Map::new_from_reader(input)?;
+    let start_positions = map.find_field('^');
+
+    if start_positions.len() != 1 {
+        return Err(format!(
+            "Expected exactly one starting position '^', found {}",
+            start_positions.len()
+        )
+        .into());
+    }
+
+    Ok(count_obstruction_points(&map, start_positions[0]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = include_str!("../input.dev");
+
+    #[test]
+    fn parsers_never_panic() {
+        fuzz::parses_input(EXAMPLE, |input| parse(input));
+    }
+}
@@ -0,0 +1,77 @@
+use aoc::days::{self, Day};
+
+// The year/day/part/input selection shared by all commands.
+pub struct Selection {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input: String,
+}
+
+impl Selection {
+    pub fn new(year: Option<String>, day: Option<String>) -> Result<Self, String> {
+        let year = year
+            .ok_or("missing year")?
+            .parse()
+            .map_err(|_| "year must be a number")?;
+        let day = match day.ok_or("missing day")?.as_str() {
+            "all" => None,
+            day => Some(day.parse().map_err(|_| "day must be a number or 'all'")?),
+        };
+        Ok(Self {
+            year,
+            day,
+            part: None,
+            input: "prod".to_string(),
+        })
+    }
+
+    // Consumes `--part` and `--input`, returns false for any other option.
+    pub fn option<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        match arg {
+            "--part" | "-p" => match args.next().as_deref() {
+                Some("1") => self.part = Some(1),
+                Some("2") => self.part = Some(2),
+                _ => return Err("part must be 1 or 2".into()),
+            },
+            "--input" | "-i" => {
+                self.input = args.next().ok_or("missing input after --input")?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn days(&self) -> Result<Vec<&'static Day>, String> {
+        let selected: Vec<&Day> = match self.day {
+            Some(day) => vec![days::find(self.year, day)
+                .ok_or_else(|| format!("{}/{} is not registered", self.year, day))?],
+            None => days::of_year(self.year).collect(),
+        };
+        if selected.is_empty() {
+            return Err(format!("no days registered for {}", self.year));
+        }
+        Ok(selected)
+    }
+
+    pub fn parts(&self, day: &Day) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            // Running a whole year skips the parts that were never solved.
+            None if self.day.is_none() => [1, 2]
+                .into_iter()
+                .filter(|&p| day.part(p).is_some())
+                .collect(),
+            None => vec![1, 2],
+        }
+    }
+}
+
+pub fn number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| format!("{} expects a number", flag))
+}
@@ -0,0 +1,251 @@
+// Every answer submitted for a day and what the site said about it, kept in
+// an `attempts.toml` file next to the inputs of the day:
+//
+//     [[attempt]]
+//     part = 1
+//     answer = "24001"
+//     verdict = "too high"
+//     time = 1701406812
+//
+// The time is in seconds since the Unix epoch. Only answers the site judged
+// are recorded, not the ones it refused to look at (e.g. rate limited).
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const ATTEMPTS_FILE: &str = "attempts.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong without a hint on which side.
+    Wrong,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+        ]
+        .into_iter()
+        .find(|v| v.as_str() == s)
+        .ok_or_else(|| format!("unknown verdict '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub time: u64,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[[attempt]]")?;
+        writeln!(f, "part = {}", self.part)?;
+        writeln!(f, "answer = {}", toml::Value::from(self.answer.as_str()))?;
+        writeln!(f, "verdict = \"{}\"", self.verdict)?;
+        writeln!(f, "time = {}", self.time)
+    }
+}
+
+// What the attempts so far tell about a new answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check<'a> {
+    // Nothing is known against it.
+    Plausible,
+    // The part is solved already.
+    Solved(&'a Attempt),
+    // The very same answer was submitted before.
+    Repeated(&'a Attempt),
+    // A numeric answer at or beyond an answer known to be too low or too high.
+    OutOfBounds(&'a Attempt),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attempts {
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    // Loads the attempts for the day in `day_dir`, none if there is no file.
+    pub fn load<P: AsRef<Path>>(day_dir: P) -> Result<Self, Box<dyn Error>> {
+        let path = day_dir.as_ref().join(ATTEMPTS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|e| format!("{}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+        }
+    }
+
+    // Adds `attempt` to the file of the day in `day_dir` as well.
+    pub fn record<P: AsRef<Path>>(&mut self, day_dir: P, attempt: Attempt) -> io::Result<()> {
+        let path = day_dir.as_ref().join(ATTEMPTS_FILE);
+        let separator = if self.attempts.is_empty() { "" } else { "\n" };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        write!(file, "{}{}", separator, attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    pub fn of_part(&self, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.part == part)
+    }
+
+    pub fn check(&self, part: u8, answer: &str) -> Check<'_> {
+        if let Some(correct) = self.of_part(part).find(|a| a.verdict == Verdict::Correct) {
+            return Check::Solved(correct);
+        }
+        if let Some(same) = self.of_part(part).find(|a| a.answer == answer) {
+            return Check::Repeated(same);
+        }
+        let Ok(value) = answer.parse::<i64>() else {
+            return Check::Plausible;
+        };
+        let bound = self
+            .of_part(part)
+            .find(|a| match (a.verdict, a.answer.parse::<i64>()) {
+                (Verdict::TooHigh, Ok(high)) => value >= high,
+                (Verdict::TooLow, Ok(low)) => value <= low,
+                _ => false,
+            });
+        bound.map_or(Check::Plausible, Check::OutOfBounds)
+    }
+}
+
+impl FromStr for Attempts {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table: toml::Table = s.parse()?;
+        let Some(attempts) = table.remove("attempt") else {
+            return Ok(Self::default());
+        };
+        if let Some(key) = table.keys().next() {
+            return Err(format!("unknown key '{}'", key).into());
+        }
+        let attempts = attempts
+            .as_array()
+            .ok_or("'attempt' must be an array of tables")?;
+        let attempts = attempts
+            .iter()
+            .enumerate()
+            .map(|(i, attempt)| {
+                let field = |key: &str| {
+                    attempt
+                        .get(key)
+                        .ok_or_else(|| format!("attempt #{}: missing '{}'", i + 1, key))
+                };
+                let invalid = |key: &str| format!("attempt #{}: invalid '{}'", i + 1, key);
+                Ok(Attempt {
+                    part: field("part")?
+                        .as_integer()
+                        .filter(|p| (1..=2).contains(p))
+                        .ok_or_else(|| invalid("part"))? as u8,
+                    answer: field("answer")?
+                        .as_str()
+                        .ok_or_else(|| invalid("answer"))?
+                        .to_string(),
+                    verdict: field("verdict")?
+                        .as_str()
+                        .ok_or_else(|| invalid("verdict"))?
+                        .parse()?,
+                    time: field("time")?
+                        .as_integer()
+                        .and_then(|t| t.try_into().ok())
+                        .ok_or_else(|| invalid("time"))?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { attempts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            part,
+            answer: answer.to_string(),
+            verdict,
+            time: 1701406812,
+        }
+    }
+
+    #[test]
+    fn attempts_round_trip() {
+        let attempts = [
+            attempt(1, "24001", Verdict::TooHigh),
+            attempt(2, "a \"b\"", Verdict::Wrong),
+        ];
+        let text = format!("{}\n{}", attempts[0], attempts[1]);
+        let parsed: Attempts = text.parse().unwrap();
+        assert_eq!(parsed.attempts, attempts);
+        assert!(
+            "[[attempt]]\npart = 3\nanswer = \"1\"\nverdict = \"wrong\"\ntime = 0\n"
+                .parse::<Attempts>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn answers_are_checked_against_bounds() {
+        let attempts = Attempts {
+            attempts: vec![
+                attempt(1, "100", Verdict::TooHigh),
+                attempt(1, "10", Verdict::TooLow),
+                attempt(1, "50", Verdict::Wrong),
+                attempt(2, "7", Verdict::Correct),
+            ],
+        };
+        assert_eq!(attempts.check(1, "42"), Check::Plausible);
+        assert_eq!(attempts.check(1, "abc"), Check::Plausible);
+        assert_eq!(
+            attempts.check(1, "50"),
+            Check::Repeated(&attempts.attempts[2])
+        );
+        assert_eq!(
+            attempts.check(1, "101"),
+            Check::OutOfBounds(&attempts.attempts[0])
+        );
+        assert_eq!(
+            attempts.check(1, "9"),
+            Check::OutOfBounds(&attempts.attempts[1])
+        );
+        assert_eq!(attempts.check(2, "8"), Check::Solved(&attempts.attempts[3]));
+    }
+}
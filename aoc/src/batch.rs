@@ -0,0 +1,288 @@
+// Runs the parts of days on every input found in their directories (`input.dev`,
+// `input2.dev`, `input.prod`, ...), the Rust solutions as well as the Python
+// ones, and tabulates the answers side by side, checked against the answers
+// recorded in `answers.toml` where there are some.
+//
+// Answers that differ from the recorded ones fail the batch, and so do errors
+// on inputs with a recorded answer. Errors elsewhere are shown but expected:
+// examples often only fit one of the parts.
+
+use crate::python::{self, PythonDay};
+use crate::{remember, report, Run};
+use aoc::days::{self, Day};
+use aoc::history::{self, LastRun};
+use aoc::root_dir;
+use aoc_common::answers::Answers;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+// The outcome of one part on one input, by either solver.
+struct Cell {
+    part: u8,
+    answer: Result<String, String>,
+    elapsed: Option<Duration>,
+    expected: Option<String>,
+    json: Value,
+    last_run: LastRun,
+}
+
+impl Cell {
+    fn is_correct(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(self.answer.as_ref().is_ok_and(|answer| answer == expected))
+    }
+
+    fn text(&self) -> String {
+        let answer = match &self.answer {
+            Ok(answer) => answer.as_str(),
+            Err(_) => "error",
+        };
+        match (self.is_correct(), &self.expected) {
+            (Some(true), _) => format!("{} (ok)", answer),
+            (Some(false), Some(expected)) => format!("{} (expected {})", answer, expected),
+            _ => answer.to_string(),
+        }
+    }
+}
+
+// The parts of one day, by one solver, on one input.
+struct Row {
+    day: String,
+    solver: &'static str,
+    input: String,
+    cells: Vec<Cell>,
+}
+
+// The selectors of the inputs in `day_dir`: `dev` for `input.dev`, the file
+// name for the others.
+fn inputs(day_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(day_dir).map_err(|e| format!("{}: {}", day_dir.display(), e))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        // `input.part` is a download that did not finish
+        .filter(|name| name.starts_with("input") && name != "input.part")
+        .collect();
+    names.sort();
+    Ok(names
+        .into_iter()
+        .map(|name| match name.strip_prefix("input.") {
+            Some(variant) => variant.to_string(),
+            None => name,
+        })
+        .collect())
+}
+
+fn rust_row(day: &'static Day, parts: &[u8], input: &str, answers: &Answers, time: u64) -> Row {
+    let cells = parts
+        .iter()
+        .filter(|&&part| day.part(part).is_some())
+        .map(|&part| {
+            let run = Run::new(day, part, input);
+            if run.answer.is_err() {
+                run.print(false);
+            }
+            let mut json = run.to_json();
+            json["solver"] = json!("rust");
+            Cell {
+                part,
+                elapsed: run.elapsed,
+                expected: answers.get(input, part).map(str::to_string),
+                last_run: run.last_run(time),
+                answer: run.answer,
+                json,
+            }
+        })
+        .collect();
+    Row {
+        day: format!("{}/{:02}", day.year, day.day),
+        solver: "rust",
+        input: input.to_string(),
+        cells,
+    }
+}
+
+fn python_row(day: &PythonDay, parts: &[u8], input: &str, answers: &Answers, time: u64) -> Row {
+    let parts: Vec<u8> = parts.iter().copied().filter(|&p| day.has_part(p)).collect();
+    let cells = python::run_day(&root_dir(), day, &parts, input, true)
+        .into_iter()
+        .map(|run| {
+            if run.answer.is_err() {
+                run.print();
+            }
+            Cell {
+                part: run.part,
+                elapsed: run.elapsed,
+                expected: answers.get(input, run.part).map(str::to_string),
+                json: run.to_json(),
+                last_run: run.last_run(time),
+                answer: run.answer,
+            }
+        })
+        .collect();
+    Row {
+        day: format!("{}/{:02}", day.year, day.day),
+        solver: "python",
+        input: input.to_string(),
+        cells,
+    }
+}
+
+fn print_table(rows: &[Row], parts: &[u8]) {
+    let mut header = vec!["day".to_string(), "solver".to_string(), "input".to_string()];
+    for part in parts {
+        header.push(format!("part {}", part));
+        header.push("time".to_string());
+    }
+    let mut lines = vec![header];
+    for row in rows {
+        let mut line = vec![row.day.clone(), row.solver.to_string(), row.input.clone()];
+        for &part in parts {
+            match row.cells.iter().find(|c| c.part == part) {
+                Some(cell) => {
+                    line.push(cell.text());
+                    line.push(match (cell.elapsed, &cell.answer) {
+                        (Some(elapsed), Ok(_)) => format!("{:.2?}", elapsed),
+                        _ => "-".to_string(),
+                    });
+                }
+                None => line.extend(["-".to_string(), "-".to_string()]),
+            }
+        }
+        lines.push(line);
+    }
+    let widths: Vec<usize> = (0..lines[0].len())
+        .map(|i| lines.iter().map(|line| line[i].len()).max().unwrap_or(0))
+        .collect();
+    for line in lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let year: u16 = args
+        .next()
+        .ok_or("missing year")?
+        .parse()
+        .map_err(|_| "year must be a number")?;
+    let day = match args.next().ok_or("missing day")?.as_str() {
+        "all" => None,
+        day => Some(
+            day.parse::<u8>()
+                .map_err(|_| "day must be a number or 'all'")?,
+        ),
+    };
+    let mut parts = vec![1, 2];
+    let mut json = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => match args.next().as_deref() {
+                Some("1") => parts = vec![1],
+                Some("2") => parts = vec![2],
+                _ => return Err("part must be 1 or 2".into()),
+            },
+            "--json" => json = Some(args.next().ok_or("missing json file")?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let root = root_dir();
+    let selected = |d: u8| day.is_none_or(|day| day == d);
+    let rust_days: Vec<&Day> = days::of_year(year).filter(|d| selected(d.day)).collect();
+    // Years without Python solutions have no directory to look into either.
+    let python_days: Vec<PythonDay> = python::python_days(&root, year)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|d| selected(d.day))
+        .collect();
+    let mut numbers: Vec<u8> = rust_days
+        .iter()
+        .map(|d| d.day)
+        .chain(python_days.iter().map(|d| d.day))
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+    if numbers.is_empty() {
+        return Err(match day {
+            Some(day) => format!("{}/{} has no solution", year, day),
+            None => format!("no solutions for {}", year),
+        });
+    }
+
+    let mut rows = Vec::new();
+    let time = history::now();
+    for number in numbers {
+        let rust = rust_days.iter().find(|d| d.day == number);
+        let python = python_days.iter().find(|d| d.day == number);
+        let day_dir = match (rust, python) {
+            (Some(rust), _) => root.join(rust.dir),
+            (None, Some(python)) => root.join(&python.dir),
+            (None, None) => unreachable!("day {} was selected", number),
+        };
+        let answers = Answers::load(&day_dir).map_err(|e| e.to_string())?;
+        for input in inputs(&day_dir)? {
+            if let Some(rust) = rust {
+                rows.push(rust_row(rust, &parts, &input, &answers, time));
+            }
+            if let Some(python) = python {
+                rows.push(python_row(python, &parts, &input, &answers, time));
+            }
+        }
+    }
+    rows.retain(|row| !row.cells.is_empty());
+
+    let cells = || rows.iter().flat_map(|row| row.cells.iter());
+    remember(cells().map(|cell| cell.last_run.clone()));
+    if json.as_deref() != Some("-") {
+        print_table(&rows, &parts);
+    }
+    if let Some(json) = &json {
+        let results: Vec<Value> = cells()
+            .map(|cell| {
+                let mut result = cell.json.clone();
+                result["expected"] = json!(cell.expected);
+                result["correct"] = json!(cell.is_correct());
+                result
+            })
+            .collect();
+        report::write(json, &json!({ "results": results }))?;
+    }
+    let wrong = cells()
+        .filter(|cell| cell.is_correct() == Some(false))
+        .count();
+    if wrong > 0 {
+        eprintln!("{} answer(s) differ from the recorded ones", wrong);
+    }
+    Ok(wrong == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_are_found_by_selector() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "input.dev",
+            "input2.dev",
+            "input.prod",
+            "input.part",
+            "answers.toml",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let found = inputs(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found.unwrap(), ["dev", "prod", "input2.dev"]);
+    }
+}
@@ -0,0 +1,441 @@
+// Times the parsing and the parts of the selected days over repeated runs and
+// compares the medians with a previously saved baseline. The memory used by
+// the first run is compared the same way, by the peak of the heap.
+
+use aoc::days::Day;
+use aoc::root_dir;
+use aoc_common::input::InputSource;
+use aoc_common::memory::{self, Bytes, Usage};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::args::{self, Selection};
+use crate::report;
+
+const BASELINE_FILE: &str = "bench-baseline.json";
+
+struct Options {
+    runs: usize,
+    // Allowed slowdown of the median, relative to the baseline.
+    threshold: f64,
+    baseline: PathBuf,
+    save_baseline: bool,
+    json: Option<String>,
+}
+
+type Phase = dyn Fn(&mut dyn BufRead) -> Result<(), Box<dyn Error>>;
+
+// Key of a measurement in the baseline: year, day, phase and input.
+type Key = (u64, u64, String, String);
+
+struct Measurement {
+    day: &'static Day,
+    phase: String,
+    input: String,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    usage: Usage,
+    baseline: Option<Duration>,
+    baseline_heap: Option<u64>,
+}
+
+impl Measurement {
+    fn key(&self) -> Key {
+        (
+            self.day.year as u64,
+            self.day.day as u64,
+            self.phase.clone(),
+            self.input.clone(),
+        )
+    }
+
+    // Relative change of the median against the baseline.
+    fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64();
+        (baseline > 0.0).then(|| self.median.as_secs_f64() / baseline - 1.0)
+    }
+
+    // Relative change of the peak of the heap against the baseline.
+    fn heap_change(&self) -> Option<f64> {
+        let baseline = self.baseline_heap? as f64;
+        let peak = self.usage.heap?.peak as f64;
+        (baseline > 0.0).then(|| peak / baseline - 1.0)
+    }
+
+    fn is_regression(&self, threshold: f64) -> bool {
+        [self.change(), self.heap_change()]
+            .into_iter()
+            .flatten()
+            .any(|change| change > threshold)
+    }
+
+    fn to_json(&self, threshold: f64) -> Value {
+        json!({
+            "year": self.day.year,
+            "day": self.day.day,
+            "phase": self.phase,
+            "input": self.input,
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "mean_ns": self.mean.as_nanos() as u64,
+            "baseline_ns": self.baseline.map(|b| b.as_nanos() as u64),
+            "change": self.change(),
+            "memory": report::memory(Some(&self.usage)),
+            "baseline_peak_heap_bytes": self.baseline_heap,
+            "heap_change": self.heap_change(),
+            "regression": self.is_regression(threshold),
+        })
+    }
+}
+
+fn measure<F>(runs: usize, input: &[u8], f: F) -> Result<([Duration; 3], Usage), Box<dyn Error>>
+where
+    F: Fn(&mut dyn BufRead) -> Result<(), Box<dyn Error>>,
+{
+    // The first run also reports errors before anything is timed, and tells
+    // the memory used.
+    let (result, usage) = memory::measure(|| f(&mut &input[..]));
+    result?;
+
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f(&mut &input[..])?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+    let mean = times.iter().sum::<Duration>() / runs as u32;
+    Ok(([times[0], times[runs / 2], mean], usage))
+}
+
+fn bench_day(
+    day: &'static Day,
+    selection: &Selection,
+    options: &Options,
+) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    let mut input = Vec::new();
+    InputSource::resolve(&selection.input, root_dir().join(day.dir))
+        .open()?
+        .read_to_end(&mut input)?;
+
+    let mut phases: Vec<(String, Box<Phase>)> = Vec::new();
+    if let Some(parse) = day.parse {
+        phases.push(("parse".to_string(), Box::new(parse)));
+    }
+    for part in selection.parts(day) {
+        if let Some(solve) = day.part(part) {
+            phases.push((
+                format!("part{}", part),
+                Box::new(move |input: &mut dyn BufRead| solve(input).map(|_| ())),
+            ));
+        }
+    }
+
+    let mut measurements = Vec::new();
+    for (phase, f) in phases {
+        let ([min, median, mean], usage) =
+            measure(options.runs, &input, f).map_err(|e| format!("{}: {}", phase, e))?;
+        measurements.push(Measurement {
+            day,
+            phase,
+            input: selection.input.clone(),
+            min,
+            median,
+            mean,
+            usage,
+            baseline: None,
+            baseline_heap: None,
+        });
+    }
+    Ok(measurements)
+}
+
+fn load_baseline(options: &Options) -> Result<BTreeMap<Key, Value>, Box<dyn Error>> {
+    let content = match fs::read_to_string(&options.baseline) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(format!("{}: {}", options.baseline.display(), e).into()),
+    };
+    let report: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{}: {}", options.baseline.display(), e))?;
+    let mut baseline = BTreeMap::new();
+    for result in report["results"].as_array().into_iter().flatten() {
+        let key = (
+            result["year"].as_u64().unwrap_or_default(),
+            result["day"].as_u64().unwrap_or_default(),
+            result["phase"].as_str().unwrap_or_default().to_string(),
+            result["input"].as_str().unwrap_or_default().to_string(),
+        );
+        baseline.insert(key, result.clone());
+    }
+    Ok(baseline)
+}
+
+// Attaches the baseline of every measurement that has one.
+fn compare(measurements: &mut [Measurement], baseline: &BTreeMap<Key, Value>) {
+    for m in measurements.iter_mut() {
+        let Some(b) = baseline.get(&m.key()) else {
+            continue;
+        };
+        m.baseline = b["median_ns"].as_u64().map(Duration::from_nanos);
+        m.baseline_heap = b["memory"]["peak_heap_bytes"].as_u64();
+    }
+}
+
+// Writes `results` into the baseline. Days that were not part of this run
+// keep their previous baseline.
+fn save_baseline(
+    options: &Options,
+    mut baseline: BTreeMap<Key, Value>,
+    measurements: &[Measurement],
+    results: Vec<Value>,
+) -> Result<(), String> {
+    for (m, result) in measurements.iter().zip(results) {
+        baseline.insert(m.key(), result);
+    }
+    let report = json!({ "results": baseline.into_values().collect::<Vec<_>>() });
+    fs::write(
+        &options.baseline,
+        serde_json::to_string_pretty(&report).unwrap() + "\n",
+    )
+    .map_err(|e| format!("{}: {}", options.baseline.display(), e))
+}
+
+fn print_table(measurements: &[Measurement], threshold: f64) {
+    println!(
+        "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10} {:>8} {:>10} {:>8}",
+        "day", "phase", "median", "min", "mean", "baseline", "change", "heap", "change"
+    );
+    for m in measurements {
+        let baseline = m
+            .baseline
+            .map(|b| format!("{:.2?}", b))
+            .unwrap_or("-".to_string());
+        let percent = |change: Option<f64>| {
+            change
+                .map(|c| format!("{:+.1}%", c * 100.0))
+                .unwrap_or("-".to_string())
+        };
+        let heap = m
+            .usage
+            .heap
+            .map(|heap| Bytes(heap.peak).to_string())
+            .unwrap_or("-".to_string());
+        println!(
+            "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10} {:>8} {:>10} {:>8}{}",
+            format!("{}/{:02}", m.day.year, m.day.day),
+            m.phase,
+            format!("{:.2?}", m.median),
+            format!("{:.2?}", m.min),
+            format!("{:.2?}", m.mean),
+            baseline,
+            percent(m.change()),
+            heap,
+            percent(m.heap_change()),
+            if m.is_regression(threshold) {
+                "  REGRESSION"
+            } else {
+                ""
+            },
+        );
+    }
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut selection = Selection::new(args.next(), args.next())?;
+    let mut options = Options {
+        runs: 10,
+        threshold: 0.1,
+        baseline: root_dir().join(BASELINE_FILE),
+        save_baseline: false,
+        json: None,
+    };
+    while let Some(arg) = args.next() {
+        if selection.option(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--runs" => options.runs = args::number(&arg, args.next())?,
+            "--threshold" => options.threshold = args::number::<f64>(&arg, args.next())? / 100.0,
+            "--baseline" => options.baseline = args.next().ok_or("missing baseline file")?.into(),
+            "--save-baseline" => options.save_baseline = true,
+            "--json" => options.json = Some(args.next().ok_or("missing json file")?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+
+    let baseline = load_baseline(&options).map_err(|e| e.to_string())?;
+    let mut ok = true;
+    let mut measurements = Vec::new();
+    for day in selection.days()? {
+        match bench_day(day, &selection, &options) {
+            Ok(day_measurements) => measurements.extend(day_measurements),
+            Err(e) => {
+                eprintln!("{} day {:>2}: {}", day.year, day.day, e);
+                ok = false;
+            }
+        }
+    }
+    compare(&mut measurements, &baseline);
+
+    if options.json.as_deref() != Some("-") {
+        print_table(&measurements, options.threshold);
+    }
+    let regressions = measurements
+        .iter()
+        .filter(|m| m.is_regression(options.threshold))
+        .count();
+    if regressions > 0 {
+        eprintln!(
+            "{} regression(s) above {:.0}%",
+            regressions,
+            options.threshold * 100.0
+        );
+        ok = false;
+    }
+
+    let results: Vec<Value> = measurements
+        .iter()
+        .map(|m| m.to_json(options.threshold))
+        .collect();
+    if let Some(json) = &options.json {
+        let report = json!({
+            "runs": options.runs,
+            "threshold": options.threshold,
+            "results": results,
+        });
+        report::write(json, &report)?;
+    }
+
+    if options.save_baseline {
+        save_baseline(&options, baseline, &measurements, results)?;
+        eprintln!("baseline saved to {}", options.baseline.display());
+    }
+
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::days;
+    use aoc_common::memory::Heap;
+    use std::{env, process};
+
+    fn measurement(year: u16, day: u8, median_ms: u64, peak: u64) -> Measurement {
+        let median = Duration::from_millis(median_ms);
+        Measurement {
+            day: days::find(year, day).unwrap(),
+            phase: "part1".to_string(),
+            input: "dev".to_string(),
+            min: median,
+            median,
+            mean: median,
+            usage: Usage {
+                heap: Some(Heap {
+                    allocations: 1,
+                    allocated: peak,
+                    peak,
+                }),
+                peak_resident: None,
+            },
+            baseline: None,
+            baseline_heap: None,
+        }
+    }
+
+    fn options(name: &str) -> Options {
+        Options {
+            runs: 1,
+            threshold: 0.1,
+            baseline: env::temp_dir().join(format!("aoc-bench-{}-{}.json", name, process::id())),
+            save_baseline: true,
+            json: None,
+        }
+    }
+
+    fn baseline_of(measurements: &[Measurement]) -> BTreeMap<Key, Value> {
+        measurements
+            .iter()
+            .map(|m| (m.key(), m.to_json(0.1)))
+            .collect()
+    }
+
+    #[test]
+    fn regressions_are_above_the_threshold() {
+        let baseline = baseline_of(&[measurement(2022, 1, 100, 1000)]);
+        for (median_ms, peak, regression) in [
+            (109, 1000, false),
+            (111, 1000, true),
+            (100, 1090, false),
+            (100, 1110, true),
+            (50, 500, false),
+        ] {
+            let mut measurements = [measurement(2022, 1, median_ms, peak)];
+            compare(&mut measurements, &baseline);
+            let m = &measurements[0];
+            assert_eq!(m.baseline, Some(Duration::from_millis(100)));
+            assert_eq!(m.baseline_heap, Some(1000));
+            assert_eq!(
+                m.is_regression(0.1),
+                regression,
+                "{} ms, {} B",
+                median_ms,
+                peak
+            );
+        }
+    }
+
+    #[test]
+    fn measurements_without_baseline_are_no_regressions() {
+        let baseline = baseline_of(&[measurement(2022, 1, 100, 1000)]);
+        let mut measurements = [measurement(2022, 2, 500, 5000)];
+        compare(&mut measurements, &baseline);
+        let m = &measurements[0];
+        assert_eq!((m.baseline, m.baseline_heap), (None, None));
+        assert_eq!((m.change(), m.heap_change()), (None, None));
+        assert!(!m.is_regression(0.1));
+        assert_eq!(m.to_json(0.1)["baseline_ns"], Value::Null);
+    }
+
+    #[test]
+    fn saving_merges_into_the_baseline() {
+        let options = options("merge");
+        // Day 1 is not run again, day 2 is run again and day 3 for the first time.
+        let previous = [
+            measurement(2022, 1, 100, 1000),
+            measurement(2022, 2, 200, 2000),
+        ];
+        let results = previous.iter().map(|m| m.to_json(0.1)).collect();
+        save_baseline(&options, BTreeMap::new(), &previous, results).unwrap();
+
+        let measurements = [
+            measurement(2022, 2, 150, 1500),
+            measurement(2022, 3, 300, 3000),
+        ];
+        let results = measurements.iter().map(|m| m.to_json(0.1)).collect();
+        let baseline = load_baseline(&options).unwrap();
+        save_baseline(&options, baseline, &measurements, results).unwrap();
+
+        let saved = load_baseline(&options);
+        fs::remove_file(&options.baseline).unwrap();
+        let medians: Vec<(u64, u64)> = saved
+            .unwrap()
+            .into_iter()
+            .map(|((_, day, _, _), result)| (day, result["median_ns"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(
+            medians,
+            [(1, 100_000_000), (2, 150_000_000), (3, 300_000_000)]
+        );
+    }
+}
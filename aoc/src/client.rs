@@ -0,0 +1,330 @@
+// Requests to the Advent of Code website, shared by the commands that need
+// them.
+//
+// The session token (the `session` cookie of a logged in browser) comes from
+// the `AOC_SESSION` environment variable or from `aoc.toml` at the repository
+// root (another file can be given with `AOC_CONFIG`), which is not committed:
+//
+//     session = "53616c7465645f5f..."
+//     # Added to the User-Agent, so the site knows whom to contact.
+//     contact = "me@example.com"
+//     # Optional, the defaults are shown.
+//     base_url = "https://adventofcode.com"
+//     throttle_seconds = 5
+//
+// Requests are throttled across runs: the time of the last one is kept in
+// `.aoc/last-request`.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const STATE_DIR: &str = ".aoc";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub contact: Option<String>,
+    pub base_url: String,
+    pub throttle: Duration,
+    // Where state kept between runs lives.
+    pub state_dir: PathBuf,
+}
+
+impl Config {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            session: None,
+            contact: None,
+            base_url: "https://adventofcode.com".to_string(),
+            throttle: Duration::from_secs(5),
+            state_dir: root.as_ref().join(STATE_DIR),
+        }
+    }
+
+    // The configuration of the repository in `root`, with the environment
+    // taking precedence over the file.
+    pub fn load<P: AsRef<Path>>(root: P) -> Result<Self, Box<dyn Error>> {
+        let root = root.as_ref();
+        let path = env::var_os("AOC_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| root.join(CONFIG_FILE));
+        let mut config = match fs::read_to_string(&path) {
+            Ok(content) => {
+                Self::parse(root, &content).map_err(|e| format!("{}: {}", path.display(), e))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::new(root),
+            Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        Ok(config)
+    }
+
+    pub fn parse(root: &Path, content: &str) -> Result<Self, Box<dyn Error>> {
+        let table: toml::Table = content.parse()?;
+        let mut config = Self::new(root);
+        for (key, value) in table {
+            let string = || {
+                value
+                    .as_str()
+                    .map(|s| s.trim().to_string())
+                    .ok_or_else(|| format!("'{}' must be a string", key))
+            };
+            match key.as_str() {
+                "session" => config.session = Some(string()?),
+                "contact" => config.contact = Some(string()?),
+                "base_url" => config.base_url = string()?.trim_end_matches('/').to_string(),
+                "throttle_seconds" => {
+                    let seconds = value
+                        .as_float()
+                        .or_else(|| value.as_integer().map(|s| s as f64))
+                        .filter(|s| *s >= 0.0)
+                        .ok_or("'throttle_seconds' must be a positive number")?;
+                    config.throttle = Duration::from_secs_f64(seconds);
+                }
+                _ => return Err(format!("unknown key '{}'", key).into()),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn user_agent(&self) -> String {
+        let agent = format!("aoc/{} (rust, ureq)", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{} contact: {}", agent, contact),
+            None => agent,
+        }
+    }
+}
+
+pub struct Client {
+    config: Config,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Result<Self, String> {
+        let session = config.session.clone().ok_or_else(|| {
+            format!(
+                "no session token, set AOC_SESSION or 'session' in {}",
+                CONFIG_FILE
+            )
+        })?;
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent())
+            .timeout(Duration::from_secs(30))
+            .build();
+        Ok(Self {
+            config,
+            session,
+            agent,
+        })
+    }
+
+    // The puzzle input of a day.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/{}/day/{}/input", self.config.base_url, year, day);
+        self.throttle()?;
+        let request = self.agent.get(&url);
+        read_response(&url, self.authorized(request).call())
+    }
+
+    // Posts a form, returning the page the site answers with.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/{}", self.config.base_url, path);
+        self.throttle()?;
+        let request = self.agent.post(&url);
+        read_response(&url, self.authorized(request).send_form(form))
+    }
+
+    fn authorized(&self, request: ureq::Request) -> ureq::Request {
+        request.set("Cookie", &format!("session={}", self.session))
+    }
+
+    // Waits until the configured time has passed since the last request.
+    fn throttle(&self) -> io::Result<()> {
+        let stamp = self.config.state_dir.join("last-request");
+        if let Ok(last) = fs::read_to_string(&stamp) {
+            if let Ok(last) = last.trim().parse::<u64>() {
+                let next = UNIX_EPOCH + Duration::from_micros(last) + self.config.throttle;
+                if let Ok(wait) = next.duration_since(SystemTime::now()) {
+                    thread::sleep(wait);
+                }
+            }
+        }
+        fs::create_dir_all(&self.config.state_dir)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        fs::write(stamp, now.as_micros().to_string())
+    }
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, Box<dyn Error>> {
+    match response {
+        Ok(response) => {
+            let mut body = String::new();
+            response.into_reader().read_to_string(&mut body)?;
+            Ok(body)
+        }
+        // The site answers 400 to bad sessions and 404 to puzzles that are
+        // not unlocked yet.
+        Err(ureq::Error::Status(status, response)) => {
+            let hint = match status {
+                400 | 401 | 403 => " (is the session token still valid?)",
+                404 => " (is the puzzle unlocked yet?)",
+                _ => "",
+            };
+            let reason = response.status_text().to_string();
+            Err(format!("{}: {} {}{}", url, status, reason, hint).into())
+        }
+        Err(e) => Err(format!("{}: {}", url, e).into()),
+    }
+}
+
+// A local HTTP server standing in for the site in tests.
+#[cfg(test)]
+pub mod mock {
+    use super::Config;
+    use std::path::Path;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+    use std::time::Duration;
+
+    // What the server saw of a request.
+    #[derive(Debug)]
+    pub struct Request {
+        pub method: String,
+        pub url: String,
+        pub cookie: Option<String>,
+        pub user_agent: Option<String>,
+        pub body: String,
+    }
+
+    pub struct Server {
+        pub url: String,
+        pub requests: Receiver<Request>,
+    }
+
+    impl Server {
+        // Answers every request with the status and body `respond` returns.
+        pub fn start<F>(respond: F) -> Self
+        where
+            F: Fn(&Request) -> (u16, String) + Send + 'static,
+        {
+            let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+            let (sender, requests) = mpsc::channel();
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let header = |name: &str| {
+                        request
+                            .headers()
+                            .iter()
+                            .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+                            .map(|h| h.value.to_string())
+                    };
+                    let mut seen = Request {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie: header("Cookie"),
+                        user_agent: header("User-Agent"),
+                        body: String::new(),
+                    };
+                    let _ = request.as_reader().read_to_string(&mut seen.body);
+                    let (status, body) = respond(&seen);
+                    let _ = request
+                        .respond(tiny_http::Response::from_string(body).with_status_code(status));
+                    if sender.send(seen).is_err() {
+                        break;
+                    }
+                }
+            });
+            Self { url, requests }
+        }
+
+        pub fn config(&self, root: &Path) -> Config {
+            Config {
+                session: Some("cafe".to_string()),
+                contact: Some("tests@example.com".to_string()),
+                base_url: self.url.clone(),
+                throttle: Duration::ZERO,
+                ..Config::new(root)
+            }
+        }
+
+        // The requests seen so far, waiting a little for `count` of them as
+        // requests are only passed on after they have been answered.
+        pub fn received(&self, count: usize) -> Vec<Request> {
+            let mut requests: Vec<Request> = self.requests.try_iter().collect();
+            while requests.len() < count {
+                match self.requests.recv_timeout(Duration::from_secs(5)) {
+                    Ok(request) => requests.push(request),
+                    Err(_) => break,
+                }
+            }
+            requests.extend(self.requests.try_iter());
+            requests
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn config_is_parsed() {
+        let root = Path::new("/repo");
+        let config = Config::parse(
+            root,
+            "session = \"cafe\\n\"\ncontact = \"me@example.com\"\nthrottle_seconds = 0.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("cafe"));
+        assert_eq!(config.throttle, Duration::from_millis(500));
+        assert_eq!(config.state_dir, root.join(STATE_DIR));
+        assert!(config.user_agent().ends_with("contact: me@example.com"));
+        assert!(Config::parse(root, "sesion = \"cafe\"").is_err());
+        assert!(Client::new(Config::new(root)).is_err());
+    }
+
+    #[test]
+    fn requests_are_authorized_and_throttled() {
+        let server = mock::Server::start(|request| match request.url.as_str() {
+            "/2022/day/1/input" => (200, "1000\n2000\n".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let root = env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let config = Config {
+            throttle: Duration::from_millis(300),
+            ..server.config(&root)
+        };
+        let client = Client::new(config).unwrap();
+
+        let start = Instant::now();
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+        let err = client.input(2022, 2).unwrap_err().to_string();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert!(err.contains("404") && err.contains("unlocked"), "{}", err);
+
+        let requests = server.received(2);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=cafe"));
+        let user_agent = requests[0].user_agent.as_deref().unwrap();
+        assert!(user_agent.contains("tests@example.com"), "{}", user_agent);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,366 @@
+use std::error::Error;
+use std::io::BufRead;
+
+pub type Parse = fn(&mut dyn BufRead) -> Result<(), Box<dyn Error>>;
+pub type Part = fn(&mut dyn BufRead) -> Result<String, Box<dyn Error>>;
+// Makes an input from a seed and a size, see `aoc generate`.
+pub type Generate = fn(u64, usize) -> String;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    // Directory of the day crate, relative to the repository root.
+    pub dir: &'static str,
+    // Loads the input without solving anything, for days where that is a
+    // separate step.
+    pub parse: Option<Parse>,
+    pub part1: Option<Part>,
+    pub part2: Option<Part>,
+    pub generate: Option<Generate>,
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<Part> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+// Wraps a day's `partN` function, so all of them share the same signature.
+macro_rules! part {
+    ($solve:path) => {{
+        fn part(input: &mut dyn BufRead) -> Result<String, Box<dyn Error>> {
+            $solve(input)
+                .map(|answer| answer.to_string())
+                .map_err(Into::into)
+        }
+        Some(part as Part)
+    }};
+}
+
+macro_rules! parse {
+    ($parse:path) => {{
+        fn parse(input: &mut dyn BufRead) -> Result<(), Box<dyn Error>> {
+            $parse(input).map_err(Into::into)
+        }
+        Some(parse as Parse)
+    }};
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2022,
+        day: 1,
+        title: "Calorie Counting",
+        dir: "2022/day1_calorie_counting",
+        parse: None,
+        part1: part!(aoc2022_day1::part1),
+        part2: part!(aoc2022_day1::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 2,
+        title: "Rock Paper Scissors",
+        dir: "2022/day2_rock_paper_scissors",
+        parse: parse!(aoc2022_day2::parse),
+        part1: part!(aoc2022_day2::part1),
+        part2: part!(aoc2022_day2::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 3,
+        title: "Rucksack Reorganization",
+        dir: "2022/day3_rucksack_organization",
+        parse: None,
+        part1: part!(aoc2022_day3::part1),
+        part2: part!(aoc2022_day3::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 4,
+        title: "Camp Cleanup",
+        dir: "2022/day4_camp_cleanup",
+        parse: None,
+        part1: part!(aoc2022_day4::part1),
+        part2: part!(aoc2022_day4::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 5,
+        title: "Supply Stacks",
+        dir: "2022/day5_supply_stacks",
+        parse: None,
+        part1: None,
+        part2: part!(aoc2022_day5::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 6,
+        title: "Tuning Trouble",
+        dir: "2022/day6_tuning_trouble",
+        parse: None,
+        part1: part!(aoc2022_day6::part1),
+        part2: part!(aoc2022_day6::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 7,
+        title: "No Space Left On Device",
+        dir: "2022/day7_no_space_left_on_device",
+        parse: parse!(aoc2022_day7::parse),
+        part1: part!(aoc2022_day7::part1),
+        part2: part!(aoc2022_day7::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 8,
+        title: "Treetop Tree House",
+        dir: "2022/day8_treetop_tree_house",
+        parse: parse!(aoc2022_day8::parse),
+        part1: part!(aoc2022_day8::part1),
+        part2: part!(aoc2022_day8::part2),
+        generate: Some(aoc2022_day8::generate),
+    },
+    Day {
+        year: 2022,
+        day: 9,
+        title: "Rope Bridge",
+        dir: "2022/day9_rope_bridge",
+        parse: None,
+        part1: part!(aoc2022_day9::part1),
+        part2: part!(aoc2022_day9::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 10,
+        title: "Cathode-Ray Tube",
+        dir: "2022/day10_cathode_ray_tube",
+        parse: None,
+        part1: part!(aoc2022_day10::part1),
+        part2: part!(aoc2022_day10::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 11,
+        title: "Monkey in the Middle",
+        dir: "2022/day11_monkey_in_the_middle",
+        parse: parse!(aoc2022_day11::parse),
+        part1: part!(aoc2022_day11::part1),
+        part2: part!(aoc2022_day11::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 12,
+        title: "Hill Climbing Algorithm",
+        dir: "2022/day12_hill_climbing_algorithm",
+        parse: parse!(aoc2022_day12::parse),
+        part1: part!(aoc2022_day12::part1),
+        part2: part!(aoc2022_day12::part2),
+        generate: Some(aoc2022_day12::generate),
+    },
+    Day {
+        year: 2022,
+        day: 13,
+        title: "Distress Signal",
+        dir: "2022/day13_distress_signal",
+        parse: None,
+        part1: None,
+        part2: part!(aoc2022_day13::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 14,
+        title: "Regolith Reservoir",
+        dir: "2022/day14_regolith_resrvoir",
+        parse: parse!(aoc2022_day14::parse),
+        part1: None,
+        part2: part!(aoc2022_day14::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 15,
+        title: "Beacon Exclusion Zone",
+        dir: "2022/day15_beacon_exclusion_zone",
+        parse: parse!(aoc2022_day15::parse),
+        part1: part!(aoc2022_day15::part1),
+        part2: part!(aoc2022_day15::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 16,
+        title: "Proboscidea Volcanium",
+        dir: "2022/day16_proboscidea_volcanium",
+        parse: None,
+        part1: part!(aoc2022_day16::part1),
+        part2: None,
+        generate: Some(aoc2022_day16::generate),
+    },
+    Day {
+        year: 2022,
+        day: 17,
+        title: "Pyroclastic Flow",
+        dir: "2022/day17_pyroclastic_flow",
+        parse: parse!(aoc2022_day17::parse),
+        part1: part!(aoc2022_day17::part1),
+        part2: part!(aoc2022_day17::part2),
+        generate: Some(aoc2022_day17::generate),
+    },
+    Day {
+        year: 2022,
+        day: 18,
+        title: "Boiling Boulders",
+        dir: "2022/day18_boiling_boulders",
+        parse: parse!(aoc2022_day18::parse),
+        part1: part!(aoc2022_day18::part1),
+        part2: part!(aoc2022_day18::part2),
+        generate: None,
+    },
+    Day {
+        year: 2022,
+        day: 19,
+        title: "Not Enough Minerals",
+        dir: "2022/day19_not_enough_materials",
+        parse: None,
+        part1: part!(aoc2022_day19::part1),
+        part2: None,
+        generate: Some(aoc2022_day19::generate),
+    },
+    Day {
+        year: 2022,
+        day: 21,
+        title: "Monkey Math",
+        dir: "2022/day21_monkey_math",
+        parse: parse!(aoc2022_day21::parse),
+        part1: part!(aoc2022_day21::part1),
+        part2: part!(aoc2022_day21::part2),
+        generate: Some(aoc2022_day21::generate),
+    },
+    Day {
+        year: 2023,
+        day: 1,
+        title: "Trebuchet?!",
+        dir: "2023/day1_trebuchet",
+        parse: None,
+        part1: part!(day1_trebuchet::part1),
+        part2: part!(day1_trebuchet::part2),
+        generate: None,
+    },
+    Day {
+        year: 2023,
+        day: 2,
+        title: "Cube Conundrum",
+        dir: "2023/day2_cube_conundrum",
+        parse: parse!(day2_cube_conundrum::parse),
+        part1: part!(day2_cube_conundrum::part1),
+        part2: part!(day2_cube_conundrum::part2),
+        generate: None,
+    },
+    Day {
+        year: 2023,
+        day: 3,
+        title: "Gear Ratios",
+        dir: "2023/day3_gear_ratios",
+        parse: None,
+        part1: part!(day3_gear_ratios::part1),
+        part2: part!(day3_gear_ratios::part2),
+        generate: None,
+    },
+    Day {
+        year: 2023,
+        day: 4,
+        title: "Scratchcards",
+        dir: "2023/day4_scratchcards",
+        parse: parse!(day4_scratchcards::parse),
+        part1: part!(day4_scratchcards::part1),
+        part2: part!(day4_scratchcards::part2),
+        generate: None,
+    },
+    Day {
+        year: 2023,
+        day: 5,
+        title: "If You Give A Seed A Fertilizer",
+        dir: "2023/day5_seed_fertilizer",
+        parse: parse!(day5_seed_fertilizer::parse),
+        part1: part!(day5_seed_fertilizer::part1),
+        part2: part!(day5_seed_fertilizer::part2),
+        generate: None,
+    },
+    Day {
+        year: 2023,
+        day: 6,
+        title: "Wait For It",
+        dir: "2023/day6_wait_for_it",
+        parse: None,
+        part1: part!(day6_wait_for_it::part1),
+        part2: part!(day6_wait_for_it::part2),
+        generate: None,
+    },
+    Day {
+        year: 2024,
+        day: 6,
+        title: "Guard Gallivant",
+        dir: "2024/day6_guard_gallivant",
+        parse: parse!(day6_guard_gallivant::parse),
+        part1: None,
+        part2: part!(day6_guard_gallivant::part2),
+        generate: None,
+    },
+    Day {
+        year: 2024,
+        day: 17,
+        title: "Chronospatial Computer",
+        dir: "2024/day17_chronospatial_computer",
+        parse: parse!(day17_chronospatial_computer::parse),
+        part1: part!(day17_chronospatial_computer::part1),
+        part2: None,
+        generate: None,
+    },
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub fn of_year(year: u16) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    #[test]
+    fn days_are_unique() {
+        let mut seen = HashSet::new();
+        for day in DAYS {
+            assert!(seen.insert((day.year, day.day)), "{}/{}", day.year, day.day);
+        }
+    }
+
+    #[test]
+    fn day_dirs_exist() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for day in DAYS {
+            assert!(root.join(day.dir).is_dir(), "{}", day.dir);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// Downloads puzzle inputs into the day crates, as `input.prod` next to the
+// `input.dev` of the examples.
+//
+// Inputs never change, so one that is already there is never requested again
+// (delete the file to download it anew). See `client.rs` for the session
+// token and the throttling of requests.
+
+use crate::args::Selection;
+use crate::client::{Client, Config};
+use aoc::known::{self, KnownDay};
+use aoc::root_dir;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+pub const INPUT_FILE: &str = "input.prod";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Fetched {
+    Cached,
+    Downloaded(usize),
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let selection = Selection::new(args.next(), args.next())?;
+    if let Some(arg) = args.next() {
+        return Err(format!("unexpected argument '{}'", arg));
+    }
+    let root = root_dir();
+    let days = selected_days(&root, &selection)?;
+    let config = Config::load(&root).map_err(|e| e.to_string())?;
+    // The client is only needed, and the token only required, for missing
+    // inputs.
+    let mut client = None;
+
+    let mut ok = true;
+    for day in days {
+        match fetch(&root, &day, &config, &mut client) {
+            Ok(Fetched::Cached) => println!("{}/{}: {} is cached", day.year, day.day, INPUT_FILE),
+            Ok(Fetched::Downloaded(bytes)) => {
+                println!("{}/{}: downloaded {} bytes", day.year, day.day, bytes)
+            }
+            Err(e) => {
+                eprintln!("{}/{}: {}", day.year, day.day, e);
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn selected_days(root: &Path, selection: &Selection) -> Result<Vec<KnownDay>, String> {
+    let days: Vec<KnownDay> = known::load(root)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|day| day.year == selection.year)
+        .filter(|day| selection.day.is_none_or(|d| day.day == d))
+        .collect();
+    if days.is_empty() {
+        return Err(match selection.day {
+            Some(day) => format!(
+                "{}/{} has no crate yet, create it with 'aoc new {} {} <title>'",
+                selection.year, day, selection.year, day
+            ),
+            None => format!(
+                "no days of {} in {}",
+                selection.year,
+                known::KNOWN_DAYS_FILE
+            ),
+        });
+    }
+    Ok(days)
+}
+
+// Downloads the input of `day` unless it is there already, creating the
+// client on the first download.
+fn fetch(
+    root: &Path,
+    day: &KnownDay,
+    config: &Config,
+    client: &mut Option<Client>,
+) -> Result<Fetched, Box<dyn Error>> {
+    let path = root.join(&day.dir).join(INPUT_FILE);
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+    let client = match client {
+        Some(client) => client,
+        None => client.insert(Client::new(config.clone())?),
+    };
+    let input = client.input(day.year, day.day)?;
+    // Written in one go so that a failed download leaves no partial input.
+    let partial = path.with_extension("part");
+    fs::write(&partial, &input).map_err(|e| format!("{}: {}", partial.display(), e))?;
+    fs::rename(&partial, &path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(input.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock;
+    use crate::new;
+
+    #[test]
+    fn inputs_are_downloaded_once() {
+        let server = mock::Server::start(|request| match request.url.as_str() {
+            "/2022/day/3/input" => (200, "vJrwpWtwJgWrhcsFMMfFFhFp\n".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let root = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let day = |day: u8| KnownDay {
+            year: 2022,
+            day,
+            title: "Test".to_string(),
+            dir: format!("2022/day{}_test", day),
+            package: format!("aoc2022_day{}", day),
+        };
+        fs::create_dir_all(root.join(day(3).dir)).unwrap();
+        fs::create_dir_all(root.join(day(4).dir)).unwrap();
+        let config = server.config(&root);
+        let mut client = None;
+
+        assert_eq!(
+            fetch(&root, &day(3), &config, &mut client).unwrap(),
+            Fetched::Downloaded(25)
+        );
+        let path = root.join(day(3).dir).join(INPUT_FILE);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "vJrwpWtwJgWrhcsFMMfFFhFp\n"
+        );
+        assert_eq!(
+            fetch(&root, &day(3), &Config::new(&root), &mut None).unwrap(),
+            Fetched::Cached
+        );
+        assert!(fetch(&root, &day(4), &config, &mut client).is_err());
+        assert!(!root.join(day(4).dir).join(INPUT_FILE).exists());
+
+        let requests = server.received(2);
+        let urls: Vec<&str> = requests.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(urls, ["/2022/day/3/input", "/2022/day/4/input"]);
+        assert!(requests.iter().all(|r| r.method == "GET"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn new_days_are_fetched() {
+        let server = mock::Server::start(|request| match request.url.as_str() {
+            "/2025/day/1/input" => (
+                200,
+                "1721
+979
+"
+                .to_string(),
+            ),
+            _ => (404, "Not Found".to_string()),
+        });
+        let root = std::env::temp_dir().join(format!("aoc-fetch-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        // the template as it is in the repository, placeholder inputs included
+        let template = root.join("2022/day_template");
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::write(template.join("Cargo.toml"), "name = \"day_template\"\n").unwrap();
+        fs::write(template.join("input.dev"), "input dev").unwrap();
+        fs::write(template.join(INPUT_FILE), "input prod").unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"aoc\",\n]\n").unwrap();
+        fs::write(root.join(known::KNOWN_DAYS_FILE), "").unwrap();
+
+        let day = new::known_day(2025, 1, "First Day");
+        new::create(&root, &day).unwrap();
+        let fetched = fetch(&root, &day, &server.config(&root), &mut None);
+        let input = fs::read_to_string(root.join(&day.dir).join(INPUT_FILE));
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(fetched.unwrap(), Fetched::Downloaded(9));
+        assert_eq!(input.unwrap(), "1721\n979\n");
+    }
+}
@@ -0,0 +1,45 @@
+// Writes a synthetic input for a day, to stress solvers with inputs much
+// larger than the puzzle ones. The same seed always gives the same input, and
+// what the size counts depends on the day (trees per side of the forest,
+// valves, blueprints, jets, ...). Inputs go to the standard output, ready to
+// be piped into a run or a benchmark:
+//
+//     aoc generate 2022 16 200 --seed 7 | aoc bench 2022 16 --input -
+
+use crate::args;
+use aoc::days;
+use std::fs;
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let year: u16 = args
+        .next()
+        .ok_or("missing year")?
+        .parse()
+        .map_err(|_| "year must be a number")?;
+    let day: u8 = args
+        .next()
+        .ok_or("missing day")?
+        .parse()
+        .map_err(|_| "day must be a number")?;
+    let size: usize = args::number("size", args.next())?;
+    let mut seed = 0;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = args::number(&arg, args.next())?,
+            "--output" | "-o" => output = Some(args.next().ok_or("missing output file")?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let generate = days::find(year, day)
+        .ok_or_else(|| format!("{}/{} is not registered", year, day))?
+        .generate
+        .ok_or_else(|| format!("{}/{} has no input generator", year, day))?;
+    let input = generate(seed, size);
+    match output {
+        Some(path) => fs::write(&path, input).map_err(|e| format!("{}: {}", path, e))?,
+        None => print!("{}", input),
+    }
+    Ok(true)
+}
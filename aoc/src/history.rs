@@ -0,0 +1,212 @@
+// The last run of every part, by solver and input, kept in `runs.toml` of the
+// state directory (`.aoc/` at the repository root, not committed):
+//
+//     [[run]]
+//     year = 2022
+//     day = 1
+//     part = 1
+//     solver = "rust"
+//     input = "prod"
+//     answer = "67658"
+//     elapsed_ns = 621430
+//     time = 1701406812
+//
+// Runs that failed have an `error` instead of an `answer`. The time is in
+// seconds since the Unix epoch. A new run of a part replaces the previous one
+// with the same solver and input, so the file does not grow with every run.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const HISTORY_FILE: &str = "runs.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastRun {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    // `rust` or `python`.
+    pub solver: String,
+    pub input: String,
+    pub answer: Result<String, String>,
+    pub elapsed: Option<Duration>,
+    pub time: u64,
+}
+
+impl LastRun {
+    fn is_same(&self, other: &LastRun) -> bool {
+        (self.year, self.day, self.part) == (other.year, other.day, other.part)
+            && (&self.solver, &self.input) == (&other.solver, &other.input)
+    }
+}
+
+impl fmt::Display for LastRun {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[[run]]")?;
+        writeln!(f, "year = {}", self.year)?;
+        writeln!(f, "day = {}", self.day)?;
+        writeln!(f, "part = {}", self.part)?;
+        writeln!(f, "solver = {}", toml::Value::from(self.solver.as_str()))?;
+        writeln!(f, "input = {}", toml::Value::from(self.input.as_str()))?;
+        match &self.answer {
+            Ok(answer) => writeln!(f, "answer = {}", toml::Value::from(answer.as_str()))?,
+            Err(error) => writeln!(f, "error = {}", toml::Value::from(error.as_str()))?,
+        }
+        if let Some(elapsed) = self.elapsed {
+            writeln!(f, "elapsed_ns = {}", elapsed.as_nanos())?;
+        }
+        writeln!(f, "time = {}", self.time)
+    }
+}
+
+// The time of a run happening now.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    runs: Vec<LastRun>,
+}
+
+impl History {
+    // Loads the runs kept in `state_dir`, none if there is no file.
+    pub fn load<P: AsRef<Path>>(state_dir: P) -> Result<Self, Box<dyn Error>> {
+        let path = state_dir.as_ref().join(HISTORY_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|e| format!("{}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, state_dir: P) -> io::Result<()> {
+        fs::create_dir_all(&state_dir)?;
+        let content: Vec<String> = self.runs.iter().map(LastRun::to_string).collect();
+        fs::write(state_dir.as_ref().join(HISTORY_FILE), content.join("\n"))
+    }
+
+    pub fn record(&mut self, run: LastRun) {
+        match self.runs.iter_mut().find(|r| r.is_same(&run)) {
+            Some(last) => *last = run,
+            None => self.runs.push(run),
+        }
+    }
+
+    // The last runs of `part`, one per solver and input.
+    pub fn of_part(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &LastRun> {
+        self.runs
+            .iter()
+            .filter(move |r| (r.year, r.day, r.part) == (year, day, part))
+    }
+}
+
+impl FromStr for History {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table: toml::Table = s.parse()?;
+        let Some(runs) = table.remove("run") else {
+            return Ok(Self::default());
+        };
+        if let Some(key) = table.keys().next() {
+            return Err(format!("unknown key '{}'", key).into());
+        }
+        let runs = runs.as_array().ok_or("'run' must be an array of tables")?;
+        let runs = runs
+            .iter()
+            .enumerate()
+            .map(|(i, run)| {
+                let invalid = |key: &str| format!("run #{}: invalid '{}'", i + 1, key);
+                let field = |key: &str| {
+                    run.get(key)
+                        .ok_or_else(|| format!("run #{}: missing '{}'", i + 1, key))
+                };
+                let number = |key: &str| {
+                    field(key)?
+                        .as_integer()
+                        .and_then(|n| u64::try_from(n).ok())
+                        .ok_or_else(|| invalid(key))
+                };
+                let string = |key: &str| {
+                    field(key)?
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| invalid(key))
+                };
+                let answer = match run.get("error") {
+                    Some(_) => Err(string("error")?),
+                    None => Ok(string("answer")?),
+                };
+                Ok(LastRun {
+                    year: number("year")?.try_into().map_err(|_| invalid("year"))?,
+                    day: number("day")?.try_into().map_err(|_| invalid("day"))?,
+                    part: Some(number("part")?)
+                        .filter(|p| (1..=2).contains(p))
+                        .ok_or_else(|| invalid("part"))? as u8,
+                    solver: string("solver")?,
+                    input: string("input")?,
+                    answer,
+                    elapsed: match run.get("elapsed_ns") {
+                        Some(_) => Some(Duration::from_nanos(number("elapsed_ns")?)),
+                        None => None,
+                    },
+                    time: number("time")?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { runs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(solver: &str, answer: Result<&str, &str>, time: u64) -> LastRun {
+        LastRun {
+            year: 2022,
+            day: 1,
+            part: 1,
+            solver: solver.to_string(),
+            input: "prod".to_string(),
+            answer: answer.map(str::to_string).map_err(str::to_string),
+            elapsed: Some(Duration::from_micros(621)),
+            time,
+        }
+    }
+
+    #[test]
+    fn runs_round_trip() {
+        let mut history = History::default();
+        history.record(run("rust", Ok("67658"), 1));
+        history.record(run("python", Err("main.py failed"), 2));
+        let text: Vec<String> = history.runs.iter().map(LastRun::to_string).collect();
+        assert_eq!(text.join("\n").parse::<History>().unwrap(), history);
+        assert!("[[run]]\nyear = 2022\n".parse::<History>().is_err());
+    }
+
+    #[test]
+    fn runs_replace_the_previous_one() {
+        let mut history = History::default();
+        history.record(run("rust", Err("boom"), 1));
+        history.record(run("python", Ok("67658"), 2));
+        history.record(run("rust", Ok("67658"), 3));
+        let runs: Vec<&LastRun> = history.of_part(2022, 1, 1).collect();
+        assert_eq!(
+            runs,
+            [&run("rust", Ok("67658"), 3), &run("python", Ok("67658"), 2)]
+        );
+        assert_eq!(history.of_part(2022, 1, 2).count(), 0);
+    }
+}
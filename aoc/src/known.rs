@@ -0,0 +1,149 @@
+// The machine-readable list of all day crates in the workspace, kept in
+// `days.toml` at the repository root:
+//
+//     [[day]]
+//     year = 2022
+//     day = 1
+//     title = "Calorie Counting"
+//     dir = "2022/day1_calorie_counting"
+//     package = "aoc2022_day1"
+//
+// Unlike `DAYS` it also lists the crates that are not wired into the runner,
+// e.g. days that were just created by `aoc new` and are not solved yet.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+pub const KNOWN_DAYS_FILE: &str = "days.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownDay {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    // Directory of the day crate, relative to the repository root.
+    pub dir: String,
+    pub package: String,
+}
+
+// Loads the list of the repository in `root`.
+pub fn load<P: AsRef<Path>>(root: P) -> Result<Vec<KnownDay>, Box<dyn Error>> {
+    let path = root.as_ref().join(KNOWN_DAYS_FILE);
+    let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&content).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+pub fn parse(s: &str) -> Result<Vec<KnownDay>, Box<dyn Error>> {
+    let mut table: toml::Table = s.parse()?;
+    let Some(days) = table.remove("day") else {
+        return Ok(Vec::new());
+    };
+    if let Some(key) = table.keys().next() {
+        return Err(format!("unknown key '{}'", key).into());
+    }
+    let days = days.as_array().ok_or("'day' must be an array of tables")?;
+    days.iter()
+        .enumerate()
+        .map(|(i, day)| {
+            let day = day
+                .as_table()
+                .ok_or_else(|| format!("day #{} must be a table", i + 1))?;
+            let string = |key: &str| {
+                day.get(key)
+                    .and_then(toml::Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("day #{}: missing string '{}'", i + 1, key))
+            };
+            let number = |key: &str| {
+                day.get(key)
+                    .and_then(toml::Value::as_integer)
+                    .ok_or_else(|| format!("day #{}: missing number '{}'", i + 1, key))
+            };
+            Ok(KnownDay {
+                year: number("year")?
+                    .try_into()
+                    .map_err(|_| format!("day #{}: invalid year", i + 1))?,
+                day: number("day")?
+                    .try_into()
+                    .map_err(|_| format!("day #{}: invalid day", i + 1))?,
+                title: string("title")?,
+                dir: string("dir")?,
+                package: string("package")?,
+            })
+        })
+        .collect()
+}
+
+// Adds `day` at the end of the list, leaving the existing entries untouched.
+pub fn append<P: AsRef<Path>>(root: P, day: &KnownDay) -> io::Result<()> {
+    let path = root.as_ref().join(KNOWN_DAYS_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let separator = match content.as_str() {
+        "" => "",
+        s if s.ends_with("\n\n") => "",
+        s if s.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    write!(file, "{}{}", separator, day)
+}
+
+impl fmt::Display for KnownDay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "[[day]]")?;
+        writeln!(f, "year = {}", self.year)?;
+        writeln!(f, "day = {}", self.day)?;
+        writeln!(f, "title = {}", toml::Value::from(self.title.as_str()))?;
+        writeln!(f, "dir = {}", toml::Value::from(self.dir.as_str()))?;
+        writeln!(f, "package = {}", toml::Value::from(self.package.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+    use crate::root_dir;
+
+    #[test]
+    fn entries_round_trip() {
+        let day = KnownDay {
+            year: 2022,
+            day: 1,
+            title: "Calorie \"Counting\"".to_string(),
+            dir: "2022/day1_calorie_counting".to_string(),
+            package: "aoc2022_day1".to_string(),
+        };
+        let text = format!("{}\n{}", day, day);
+        assert_eq!(parse(&text).unwrap(), vec![day.clone(), day]);
+        assert!(parse("[[day]]\nyear = 2022\n").is_err());
+    }
+
+    #[test]
+    fn registered_days_are_known() {
+        let known = load(root_dir()).unwrap();
+        for day in DAYS {
+            let entry = known
+                .iter()
+                .find(|k| (k.year, k.day) == (day.year, day.day))
+                .unwrap_or_else(|| panic!("{}/{} missing in days.toml", day.year, day.day));
+            assert_eq!(
+                (entry.title.as_str(), entry.dir.as_str()),
+                (day.title, day.dir)
+            );
+        }
+        for day in &known {
+            let manifest = fs::read_to_string(root_dir().join(&day.dir).join("Cargo.toml"))
+                .unwrap_or_else(|e| panic!("{}: {}", day.dir, e));
+            let name = format!("name = \"{}\"", day.package);
+            assert!(manifest.contains(&name), "{}: expected {}", day.dir, name);
+        }
+    }
+}
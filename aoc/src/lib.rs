@@ -0,0 +1,14 @@
+use std::path::{Path, PathBuf};
+
+pub mod attempts;
+pub mod days;
+pub mod history;
+pub mod known;
+
+// Root of the repository, the day directories are relative to it.
+pub fn root_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc crate lives inside the repository")
+        .to_path_buf()
+}
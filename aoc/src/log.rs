@@ -0,0 +1,113 @@
+// Selection of the solver logs (see `aoc_common::trace`), accepted by every
+// command:
+//
+//     -v, -vv          debug or trace logs of all days
+//     --log <filter>   filter directives where days can be given as
+//                      <year>/<day> or <year>, e.g. `--log 2022/19=trace`
+//
+// Without them `AOC_LOG` applies.
+
+use aoc::known::{self, KnownDay};
+use aoc::root_dir;
+use aoc_common::trace;
+
+// Installs the logger, returning the arguments that are not about logging.
+pub fn init(args: Vec<String>) -> Result<Vec<String>, String> {
+    let mut rest = Vec::new();
+    let mut filter = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-v" => filter = Some("debug".to_string()),
+            "-vv" => filter = Some("trace".to_string()),
+            "--log" => filter = Some(args.next().ok_or("missing filter after --log")?),
+            _ => rest.push(arg),
+        }
+    }
+    let filter = match filter {
+        Some(filter) => {
+            let days = known::load(root_dir()).map_err(|e| e.to_string())?;
+            Some(directives(&filter, &days)?)
+        }
+        None => None,
+    };
+    trace::init(filter.as_deref())?;
+    Ok(rest)
+}
+
+// Replaces the days in `filter` by the targets of their crates. A bare level
+// applies to the days only, not to the libraries they use.
+fn directives(filter: &str, days: &[KnownDay]) -> Result<String, String> {
+    let mut directives = vec!["warn".to_string()];
+    for directive in filter.split(',').filter(|d| !d.is_empty()) {
+        let (target, level) = match directive.split_once('=') {
+            Some((target, level)) => (target, Some(level)),
+            None if is_level(directive) => ("", Some(directive)),
+            None => (directive, None),
+        };
+        let selected: Vec<&KnownDay> = match target.split_once('/') {
+            _ if target.is_empty() => days.iter().collect(),
+            Some((year, day)) => days
+                .iter()
+                .filter(|d| year.parse() == Ok(d.year) && day.parse() == Ok(d.day))
+                .collect(),
+            None => match target.parse::<u16>() {
+                Ok(year) => days.iter().filter(|d| d.year == year).collect(),
+                Err(_) => {
+                    directives.push(directive.to_string());
+                    continue;
+                }
+            },
+        };
+        if selected.is_empty() {
+            return Err(format!("no day matches '{}' in --log", target));
+        }
+        for day in selected {
+            let target = day.package.replace('-', "_");
+            directives.push(match level {
+                Some(level) => format!("{}={}", target, level),
+                None => target,
+            });
+        }
+    }
+    Ok(directives.join(","))
+}
+
+fn is_level(s: &str) -> bool {
+    ["off", "error", "warn", "info", "debug", "trace"].contains(&s.to_lowercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_become_targets() {
+        let day = |year: u16, day: u8, package: &str| KnownDay {
+            year,
+            day,
+            title: String::new(),
+            dir: String::new(),
+            package: package.to_string(),
+        };
+        let days = [
+            day(2022, 17, "aoc2022_day17"),
+            day(2022, 19, "aoc2022_day19"),
+            day(2023, 5, "day5_seed_fertilizer"),
+        ];
+        let directives = |filter| directives(filter, &days);
+        assert_eq!(
+            directives("2022/19=trace").unwrap(),
+            "warn,aoc2022_day19=trace"
+        );
+        assert_eq!(
+            directives("debug").unwrap(),
+            "warn,aoc2022_day17=debug,aoc2022_day19=debug,day5_seed_fertilizer=debug"
+        );
+        assert_eq!(
+            directives("2023,aoc_common=debug").unwrap(),
+            "warn,day5_seed_fertilizer,aoc_common=debug"
+        );
+        assert!(directives("2022/20=debug").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use aoc::days::Day;
+use aoc::history::{self, History, LastRun};
+use aoc::root_dir;
+use aoc_common::input::InputSource;
+use aoc_common::memory::{self, Usage};
+use serde_json::{json, Value};
+use std::env;
+use std::error::Error;
+use std::io::Read;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+mod args;
+mod batch;
+mod bench;
+mod client;
+mod fetch;
+mod generate;
+mod log;
+mod new;
+mod python;
+mod report;
+mod status;
+mod submit;
+mod watch;
+
+use args::Selection;
+
+aoc_common::count_allocations!();
+
+const USAGE: &str = "usage:
+    aoc <year> <day|all> [--part <1|2>] [--input <dev|prod|file|->] [--json <file|->]
+        [--memory]
+    aoc bench <year> <day|all> [--part <1|2>] [--input <dev|prod|file|->]
+        [--runs <n>] [--threshold <percent>] [--baseline <file>] [--save-baseline]
+        [--json <file|->]
+    aoc new <year> <day> <title>
+    aoc fetch <year> <day|all>
+    aoc submit <year> <day> <1|2> [answer] [--force]
+    aoc generate <year> <day> <size> [--seed <n>] [--output <file>]
+    aoc python <year> <day|all> [--part <1|2>] [--input <dev|prod|file|->] [--json <file|->]
+    aoc batch <year> <day|all> [--part <1|2>] [--json <file|->]
+    aoc status [year] [--json <file|->]
+    aoc watch <year> <day> [--part <1|2>] [--input <dev|prod|file>] [--expect <answer>]
+        [--interval <ms>]
+
+Every command takes -v, -vv or --log <filter> for the logs of the solvers,
+e.g. --log 2022/19=trace (see AOC_LOG).";
+
+// The outcome of running one part on one input.
+struct Run {
+    day: &'static Day,
+    part: u8,
+    input: String,
+    digest: Option<String>,
+    elapsed: Option<Duration>,
+    usage: Option<Usage>,
+    answer: Result<String, String>,
+}
+
+impl Run {
+    fn new(day: &'static Day, part: u8, input: &str) -> Self {
+        let mut run = Self {
+            day,
+            part,
+            input: input.to_string(),
+            digest: None,
+            elapsed: None,
+            usage: None,
+            answer: Err(String::new()),
+        };
+        run.answer = run.solve().map_err(|e| e.to_string());
+        run
+    }
+
+    fn solve(&mut self) -> Result<String, Box<dyn Error>> {
+        let day = self.day;
+        let solve = day
+            .part(self.part)
+            .ok_or_else(|| format!("{}/{} part {} is not solved", day.year, day.day, self.part))?;
+        let source = InputSource::resolve(&self.input, root_dir().join(day.dir));
+        let mut input = Vec::new();
+        source.open()?.read_to_end(&mut input)?;
+        self.digest = Some(report::digest(&input));
+        let ((answer, elapsed), usage) = memory::measure(|| {
+            let start = Instant::now();
+            (solve(&mut &input[..]), start.elapsed())
+        });
+        self.elapsed = Some(elapsed);
+        self.usage = Some(usage);
+        answer.map_err(|e| match &source {
+            InputSource::File(path) => format!("{}: {}", path.display(), e).into(),
+            InputSource::Stdin => e,
+        })
+    }
+
+    // Also prints the memory used with `memory`.
+    fn print(&self, memory: bool) {
+        let day = self.day;
+        print_answer(
+            day.year,
+            day.day,
+            self.part,
+            day.title,
+            &self.answer,
+            self.elapsed,
+            self.usage.as_ref().filter(|_| memory),
+        );
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "year": self.day.year,
+            "day": self.day.day,
+            "part": self.part,
+            "title": self.day.title,
+            "answer": self.answer.as_ref().ok(),
+            "error": self.answer.as_ref().err(),
+            "input": self.input,
+            "input_sha256": self.digest,
+            "wall_time_ns": self.elapsed.map(|e| e.as_nanos() as u64),
+            "memory": report::memory(self.usage.as_ref()),
+        })
+    }
+
+    fn last_run(&self, time: u64) -> LastRun {
+        LastRun {
+            year: self.day.year,
+            day: self.day.day,
+            part: self.part,
+            solver: "rust".to_string(),
+            input: self.input.clone(),
+            answer: self.answer.clone(),
+            elapsed: self.elapsed,
+            time,
+        }
+    }
+}
+
+// Keeps the outcome of `runs` for `aoc status`. Runs are not worth failing
+// over their history, so problems only get a warning.
+fn remember(runs: impl IntoIterator<Item = LastRun>) {
+    let state_dir = root_dir().join(client::STATE_DIR);
+    let kept = History::load(&state_dir).and_then(|mut history| {
+        runs.into_iter().for_each(|run| history.record(run));
+        history.save(&state_dir).map_err(|e| {
+            format!("{}: {}", state_dir.join(history::HISTORY_FILE).display(), e).into()
+        })
+    });
+    if let Err(e) = kept {
+        eprintln!("warning: the runs are not kept: {}", e);
+    }
+}
+
+// Prints the answer of a part, or the error to the standard error.
+fn print_answer(
+    year: u16,
+    day: u8,
+    part: u8,
+    title: &str,
+    answer: &Result<String, String>,
+    elapsed: Option<Duration>,
+    usage: Option<&Usage>,
+) {
+    match answer {
+        Ok(answer) => println!(
+            "{} day {:>2} part {} ({}): {} [{:.2?}{}]",
+            year,
+            day,
+            part,
+            title,
+            answer,
+            elapsed.unwrap_or_default(),
+            usage
+                .map(|usage| format!(", {}", usage))
+                .unwrap_or_default()
+        ),
+        Err(e) => eprintln!("{} day {:>2} part {}: {}", year, day, part, e),
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut selection = Selection::new(args.next(), args.next())?;
+    let mut json = None;
+    let mut memory = false;
+    while let Some(arg) = args.next() {
+        if selection.option(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--json" => json = Some(args.next().ok_or("missing json file")?),
+            "--memory" => memory = true,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let mut runs = Vec::new();
+    for day in selection.days()? {
+        for part in selection.parts(day) {
+            let run = Run::new(day, part, &selection.input);
+            // The report takes the standard output when written there.
+            if json.as_deref() != Some("-") || run.answer.is_err() {
+                run.print(memory);
+            }
+            runs.push(run);
+        }
+    }
+    let time = history::now();
+    remember(runs.iter().map(|run| run.last_run(time)));
+    if let Some(json) = &json {
+        let results: Vec<Value> = runs.iter().map(Run::to_json).collect();
+        report::write(json, &json!({ "results": results }))?;
+    }
+    Ok(runs.iter().all(|run| run.answer.is_ok()))
+}
+
+fn main() -> ExitCode {
+    let args = match log::init(env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut args = args.into_iter().peekable();
+    let result = match args.peek().map(String::as_str) {
+        Some("bench") => bench::main(args.skip(1)),
+        Some("new") => new::main(args.skip(1)),
+        Some("fetch") => fetch::main(args.skip(1)),
+        Some("submit") => submit::main(args.skip(1)),
+        Some("python") => python::main(args.skip(1)),
+        Some("generate") => generate::main(args.skip(1)),
+        Some("batch") => batch::main(args.skip(1)),
+        Some("status") => status::main(args.skip(1)),
+        Some("watch") => watch::main(args.skip(1)),
+        Some("-h" | "--help") | None => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        _ => run(args),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// Creates the crate of a new day from `2022/day_template`, adds it to the
+// workspace and to the list of known days.
+//
+// The template is a regular crate of the workspace, so it is kept compiling.
+// While copying, `day_template` is replaced by the package name of the day
+// and `{{year}}`, `{{day}}` and `{{title}}` by the corresponding values.
+
+use crate::fetch;
+use aoc::known::{self, KnownDay};
+use aoc::root_dir;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const TEMPLATE_DIR: &str = "2022/day_template";
+const TEMPLATE_PACKAGE: &str = "day_template";
+
+// Files of the template that are not part of it. The puzzle input is left
+// for `aoc fetch`, which does not download over an existing one.
+const SKIPPED: &[&str] = &["target", "Cargo.lock", fetch::INPUT_FILE];
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let year: u16 = args
+        .next()
+        .ok_or("missing year")?
+        .parse()
+        .map_err(|_| "year must be a number")?;
+    let day: u8 = args
+        .next()
+        .ok_or("missing day")?
+        .parse()
+        .map_err(|_| "day must be a number")?;
+    if !(1..=25).contains(&day) {
+        return Err("day must be between 1 and 25".into());
+    }
+    let title = args.collect::<Vec<_>>().join(" ");
+    if slug(&title).is_empty() {
+        return Err("missing title".into());
+    }
+
+    let new_day = known_day(year, day, &title);
+    match create(&root_dir(), &new_day) {
+        Ok(()) => {
+            println!("created {} ({})", new_day.dir, new_day.package);
+            println!("register it in aoc/Cargo.toml and aoc/src/days.rs once a part is solved");
+            Ok(true)
+        }
+        Err(e) => {
+            eprintln!("{}/{}: {}", year, day, e);
+            Ok(false)
+        }
+    }
+}
+
+// Lowercase words of the title joined by underscores.
+fn slug(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub fn known_day(year: u16, day: u8, title: &str) -> KnownDay {
+    KnownDay {
+        year,
+        day,
+        title: title.to_string(),
+        dir: format!("{}/day{}_{}", year, day, slug(title)),
+        package: format!("aoc{}_day{}", year, day),
+    }
+}
+
+fn render(template: &str, day: &KnownDay) -> String {
+    template
+        .replace(TEMPLATE_PACKAGE, &day.package)
+        .replace("{{year}}", &day.year.to_string())
+        .replace("{{day}}", &day.day.to_string())
+        .replace("{{title}}", &day.title)
+}
+
+pub fn create(root: &Path, day: &KnownDay) -> Result<(), Box<dyn Error>> {
+    if known::load(root)?
+        .iter()
+        .any(|known| (known.year, known.day) == (day.year, day.day))
+    {
+        return Err(format!("already listed in {}", known::KNOWN_DAYS_FILE).into());
+    }
+    let dir = root.join(&day.dir);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_member(&fs::read_to_string(&manifest_path)?, &day.dir)?;
+
+    copy_template(&root.join(TEMPLATE_DIR), &dir, day)?;
+    fs::write(manifest_path, manifest)?;
+    known::append(root, day)?;
+    Ok(())
+}
+
+fn copy_template(from: &Path, to: &Path, day: &KnownDay) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if SKIPPED.iter().any(|skipped| name == *skipped) {
+            continue;
+        }
+        let target = to.join(&name);
+        if entry.file_type()?.is_dir() {
+            copy_template(&entry.path(), &target, day)?;
+        } else {
+            let content = fs::read_to_string(entry.path())
+                .map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+            fs::write(target, render(&content, day))?;
+        }
+    }
+    Ok(())
+}
+
+// Adds `dir` to the workspace members of the root manifest, after the last
+// member of the same year.
+fn add_member(manifest: &str, dir: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("members"))
+        .ok_or("no workspace members in Cargo.toml")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .ok_or("workspace members must end with a line of their own")?;
+    let year = format!("\"{}/", &dir[..dir.find('/').unwrap_or(dir.len())]);
+    let at = lines[start + 1..end]
+        .iter()
+        .rposition(|line| line.trim_start().starts_with(&year))
+        .map_or(end, |i| start + 2 + i);
+    let member = format!("    \"{}\",", dir);
+    lines.insert(at, &member);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_named_after_the_title() {
+        let day = known_day(2024, 10, "Hoof It");
+        assert_eq!(day.dir, "2024/day10_hoof_it");
+        assert_eq!(day.package, "aoc2024_day10");
+        assert_eq!(slug("Red-Nosed Reports"), "red_nosed_reports");
+        assert_eq!(slug("Mull It Over?"), "mull_it_over");
+    }
+
+    #[test]
+    fn template_is_rendered() {
+        let day = known_day(2024, 10, "Hoof It");
+        assert_eq!(
+            render(
+                "// {{year}} day {{day}}: {{title}}\nday_template::part1",
+                &day
+            ),
+            "// 2024 day 10: Hoof It\naoc2024_day10::part1"
+        );
+    }
+
+    #[test]
+    fn members_are_grouped_by_year() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day1_a\",\n    \"2024/day1_b\",\n]\n";
+        assert_eq!(
+            add_member(manifest, "2023/day2_c").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day1_a\",\n    \"2023/day2_c\",\n    \"2024/day1_b\",\n]\n"
+        );
+        assert_eq!(
+            add_member(manifest, "2025/day1_d").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day1_a\",\n    \"2024/day1_b\",\n    \"2025/day1_d\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn days_are_created_from_the_template() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let template = root.join(TEMPLATE_DIR);
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::create_dir_all(template.join("target")).unwrap();
+        fs::write(template.join("Cargo.toml"), "name = \"day_template\"\n").unwrap();
+        fs::write(template.join("src/lib.rs"), "// {{title}}\n").unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"aoc\",\n]\n").unwrap();
+        fs::write(root.join(known::KNOWN_DAYS_FILE), "").unwrap();
+
+        let day = known_day(2025, 1, "First Day");
+        create(&root, &day).unwrap();
+        let dir = root.join(&day.dir);
+        assert_eq!(
+            fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "name = \"aoc2025_day1\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("src/lib.rs")).unwrap(),
+            "// First Day\n"
+        );
+        assert!(!dir.join("target").exists());
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("\"2025/day1_first_day\""));
+        assert_eq!(known::load(&root).unwrap(), vec![day.clone()]);
+        assert!(create(&root, &day).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,477 @@
+// Runs the Python solutions (most of 2024) like the Rust ones, and checks
+// them against each other where a part is solved in both languages.
+//
+// A day directory can hold `part1.py` and `part2.py`, printing the answer of
+// their part on the last line, or a `main.py` printing the answers of both
+// parts, one line each and possibly labelled (`Part1: 42`). The scripts read
+// `input.prod` or `input.dev` from their working directory, so they are run
+// in a scratch directory where both are the selected input. The interpreter
+// is `python3` unless `AOC_PYTHON` names another one.
+
+use crate::args::Selection;
+use crate::{print_answer, remember, report, Run};
+use aoc::days;
+use aoc::history::{self, LastRun};
+use aoc::root_dir;
+use aoc_common::input::InputSource;
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+pub const PYTHON_VAR: &str = "AOC_PYTHON";
+
+// The input file names the scripts open.
+const INPUT_NAMES: [&str; 2] = ["input.prod", "input.dev"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Script {
+    // `partN.py`, solving part N.
+    Part(u8),
+    // `main.py`, solving the parts in order.
+    Main,
+}
+
+impl Script {
+    fn file_name(&self) -> String {
+        match self {
+            Script::Part(part) => format!("part{}.py", part),
+            Script::Main => "main.py".to_string(),
+        }
+    }
+
+    // The answer of `part` in what the script printed.
+    fn answer(&self, part: u8, output: &str) -> Option<String> {
+        let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+        let line = match self {
+            Script::Part(_) => lines.next_back()?,
+            Script::Main => lines.nth(part as usize - 1)?,
+        };
+        Some(unlabelled(line).to_string())
+    }
+}
+
+// `42` of `Part1: 42` or `part 1: 42`.
+fn unlabelled(line: &str) -> &str {
+    match line.split_once(':') {
+        Some((label, answer)) if label.to_lowercase().starts_with("part") => answer.trim(),
+        _ => line,
+    }
+}
+
+// A day with Python solutions.
+pub struct PythonDay {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    // Directory of the day, relative to the repository root.
+    pub dir: PathBuf,
+    scripts: Vec<Script>,
+}
+
+impl PythonDay {
+    pub fn has_part(&self, part: u8) -> bool {
+        self.script(part).is_some()
+    }
+
+    fn script(&self, part: u8) -> Option<&Script> {
+        self.scripts
+            .iter()
+            .find(|s| **s == Script::Part(part))
+            .or_else(|| self.scripts.iter().find(|s| **s == Script::Main))
+    }
+}
+
+// The directories of the days of `year` under `root` (`day10_hoof_it`), with
+// their day, by day.
+pub fn day_dirs(root: &Path, year: u16) -> Result<Vec<(u8, String)>, String> {
+    let year_dir = root.join(year.to_string());
+    let entries = fs::read_dir(&year_dir).map_err(|e| format!("{}: {}", year_dir.display(), e))?;
+    let mut found: Vec<(u8, String)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let day = name
+                .strip_prefix("day")
+                .and_then(|rest| rest.split_once('_'))
+                .and_then(|(day, _)| day.parse().ok())?;
+            Some((day, name))
+        })
+        .collect();
+    found.sort();
+    Ok(found)
+}
+
+// The days of `year` under `root` that have Python solutions, by day.
+pub fn python_days(root: &Path, year: u16) -> Result<Vec<PythonDay>, String> {
+    let mut found = Vec::new();
+    for (day, name) in day_dirs(root, year)? {
+        let dir = Path::new(&year.to_string()).join(&name);
+        let scripts: Vec<Script> = [Script::Part(1), Script::Part(2), Script::Main]
+            .into_iter()
+            .filter(|s| root.join(&dir).join(s.file_name()).is_file())
+            .collect();
+        if scripts.is_empty() {
+            continue;
+        }
+        let title = match days::find(year, day) {
+            Some(day) => day.title.to_string(),
+            None => title_of(&name),
+        };
+        found.push(PythonDay {
+            year,
+            day,
+            title,
+            dir,
+            scripts,
+        });
+    }
+    Ok(found)
+}
+
+// `Hoof It` of `day10_hoof_it`, for the days that have no registered title.
+pub fn title_of(dir_name: &str) -> String {
+    let slug = dir_name.split_once('_').map_or("", |(_, slug)| slug);
+    slug.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Runs `script` of the directory `dir` on `input`, returning what it printed.
+fn execute(dir: &Path, script: &Script, input: &[u8]) -> Result<String, String> {
+    static SCRATCH: AtomicUsize = AtomicUsize::new(0);
+
+    let scratch = env::temp_dir().join(format!(
+        "aoc-python-{}-{}",
+        std::process::id(),
+        SCRATCH.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&scratch).map_err(|e| format!("{}: {}", scratch.display(), e))?;
+    let output = INPUT_NAMES
+        .iter()
+        .try_for_each(|name| fs::write(scratch.join(name), input))
+        .map_err(|e| format!("{}: {}", scratch.display(), e))
+        .and_then(|_| {
+            let python = env::var(PYTHON_VAR).unwrap_or_else(|_| "python3".to_string());
+            Command::new(&python)
+                .arg(dir.join(script.file_name()))
+                .current_dir(&scratch)
+                .output()
+                .map_err(|e| format!("cannot run {}: {}", python, e))
+        });
+    let _ = fs::remove_dir_all(&scratch);
+    let output = output?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().last().unwrap_or_default();
+        return Err(format!(
+            "{} failed ({}): {}",
+            script.file_name(),
+            output.status,
+            last
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// The outcome of running the Python solution of one part on one input, and
+// of the Rust one when it is checked against it.
+pub struct PythonRun {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    pub part: u8,
+    pub input: String,
+    pub digest: Option<String>,
+    // The time of the whole script, which is both parts for `main.py`.
+    pub elapsed: Option<Duration>,
+    pub answer: Result<String, String>,
+    pub rust: Option<Run>,
+}
+
+impl PythonRun {
+    // Whether Python and Rust give the same answer, when both give one.
+    fn agrees(&self) -> Option<bool> {
+        match (&self.answer, self.rust.as_ref().map(|r| &r.answer)) {
+            (Ok(python), Some(Ok(rust))) => Some(python == rust),
+            _ => None,
+        }
+    }
+
+    pub fn print(&self) {
+        let title = format!("{}, python", self.title);
+        print_answer(
+            self.year,
+            self.day,
+            self.part,
+            &title,
+            &self.answer,
+            self.elapsed,
+            None,
+        );
+        if let Some(rust) = &self.rust {
+            rust.print(false);
+        }
+        if self.agrees() == Some(false) {
+            eprintln!(
+                "{} day {:>2} part {}: python and rust disagree",
+                self.year, self.day, self.part
+            );
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "title": self.title,
+            "solver": "python",
+            "answer": self.answer.as_ref().ok(),
+            "error": self.answer.as_ref().err(),
+            "input": self.input,
+            "input_sha256": self.digest,
+            "wall_time_ns": self.elapsed.map(|e| e.as_nanos() as u64),
+            "rust_answer": self.rust.as_ref().and_then(|r| r.answer.as_ref().ok()),
+            "agrees_with_rust": self.agrees(),
+        })
+    }
+
+    pub fn last_run(&self, time: u64) -> LastRun {
+        LastRun {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            solver: "python".to_string(),
+            input: self.input.clone(),
+            answer: self.answer.clone(),
+            elapsed: self.elapsed,
+            time,
+        }
+    }
+}
+
+// Runs the parts of `day` on `selector`, running each script once. Parts
+// that `main.py` prints no answer for are left out when `skip_unanswered`.
+pub fn run_day(
+    root: &Path,
+    day: &PythonDay,
+    parts: &[u8],
+    selector: &str,
+    skip_unanswered: bool,
+) -> Vec<PythonRun> {
+    let dir = root.join(&day.dir);
+    let mut input = Vec::new();
+    let input = InputSource::resolve(selector, &dir)
+        .open()
+        .and_then(|mut source| source.read_to_end(&mut input))
+        .map(|_| input)
+        .map_err(|e| e.to_string());
+    let mut outputs: Vec<(&Script, Result<String, String>, Duration)> = Vec::new();
+    let mut runs = Vec::new();
+    for &part in parts {
+        let mut run = PythonRun {
+            year: day.year,
+            day: day.day,
+            title: day.title.clone(),
+            part,
+            input: selector.to_string(),
+            digest: input.as_ref().ok().map(|input| report::digest(input)),
+            elapsed: None,
+            answer: Err(String::new()),
+            rust: None,
+        };
+        run.answer = match (day.script(part), &input) {
+            (None, _) => Err(format!(
+                "{}/{} part {} has no python solution",
+                day.year, day.day, part
+            )),
+            (_, Err(e)) => Err(e.clone()),
+            (Some(script), Ok(input)) => {
+                if !outputs.iter().any(|(s, _, _)| *s == script) {
+                    let start = Instant::now();
+                    let output = execute(&dir, script, input);
+                    outputs.push((script, output, start.elapsed()));
+                }
+                let (_, output, elapsed) = outputs.iter().find(|(s, _, _)| *s == script).unwrap();
+                run.elapsed = Some(*elapsed);
+                match output.as_ref().map(|output| script.answer(part, output)) {
+                    Ok(Some(answer)) => Ok(answer),
+                    Ok(None) if skip_unanswered => continue,
+                    Ok(None) => Err(format!(
+                        "{} printed no answer for part {}",
+                        script.file_name(),
+                        part
+                    )),
+                    Err(e) => Err(e.clone()),
+                }
+            }
+        };
+        runs.push(run);
+    }
+    runs
+}
+
+// The Rust solution of the same part run on the same input, if there is one.
+fn rust_run(run: &PythonRun) -> Option<Run> {
+    days::find(run.year, run.day)
+        .filter(|rust| rust.part(run.part).is_some())
+        .map(|rust| Run::new(rust, run.part, &run.input))
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut selection = Selection::new(args.next(), args.next())?;
+    let mut json = None;
+    while let Some(arg) = args.next() {
+        if selection.option(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--json" => json = Some(args.next().ok_or("missing json file")?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let root = root_dir();
+    let python_days = python_days(&root, selection.year)?;
+    let selected: Vec<&PythonDay> = match selection.day {
+        Some(day) => vec![python_days
+            .iter()
+            .find(|d| d.day == day)
+            .ok_or_else(|| format!("{}/{} has no python solution", selection.year, day))?],
+        None => python_days.iter().collect(),
+    };
+    if selected.is_empty() {
+        return Err(format!("no python solutions for {}", selection.year));
+    }
+
+    let mut runs = Vec::new();
+    let mut kept = Vec::new();
+    let time = history::now();
+    for day in selected {
+        let parts: Vec<u8> = match selection.part {
+            Some(part) => vec![part],
+            // Running a whole year skips the parts that have no script.
+            None if selection.day.is_none() => [1, 2]
+                .into_iter()
+                .filter(|&p| day.script(p).is_some())
+                .collect(),
+            None => vec![1, 2],
+        };
+        let year = selection.day.is_none();
+        for mut run in run_day(&root, day, &parts, &selection.input, year) {
+            if day.script(run.part).is_some() {
+                run.rust = rust_run(&run);
+                kept.push(run.last_run(time));
+                kept.extend(run.rust.as_ref().map(|rust| rust.last_run(time)));
+            }
+            // The report takes the standard output when written there.
+            if json.as_deref() != Some("-") || run.answer.is_err() {
+                run.print();
+            }
+            runs.push(run);
+        }
+    }
+    remember(kept);
+    if let Some(json) = &json {
+        let results: Vec<Value> = runs.iter().map(PythonRun::to_json).collect();
+        report::write(json, &json!({ "results": results }))?;
+    }
+    Ok(runs.iter().all(|run| {
+        run.answer.is_ok()
+            && run.agrees() != Some(false)
+            && run.rust.as_ref().is_none_or(|r| r.answer.is_ok())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_read_from_the_output() {
+        let main = Script::Main;
+        assert_eq!(
+            main.answer(1, "Part1: 11\nPart2: 31\n").as_deref(),
+            Some("11")
+        );
+        assert_eq!(
+            main.answer(2, "Part1: 11\n\npart 2: 31\n").as_deref(),
+            Some("31")
+        );
+        assert_eq!(main.answer(2, "480\n"), None);
+        let part = Script::Part(1);
+        assert_eq!(
+            part.answer(1, "0 3\n1 4\n55312\n").as_deref(),
+            Some("55312")
+        );
+        assert_eq!(part.answer(1, ""), None);
+        assert_eq!(title_of("day2_rednosed_reports"), "Rednosed Reports");
+    }
+
+    #[test]
+    fn scripts_read_the_selected_input() {
+        let dir = env::temp_dir().join(format!("aoc-python-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("main.py"),
+            "numbers = [int(n) for n in open('input.prod').read().split()]\n\
+             print(f'Part1: {sum(numbers)}')\n\
+             print(f'Part2: {max(numbers)}')\n",
+        )
+        .unwrap();
+        let output = execute(&dir, &Script::Main, b"3 9 4\n");
+        fs::remove_dir_all(&dir).unwrap();
+        match output {
+            Ok(output) => {
+                assert_eq!(Script::Main.answer(1, &output).as_deref(), Some("16"));
+                assert_eq!(Script::Main.answer(2, &output).as_deref(), Some("9"));
+            }
+            // Nothing to check without an interpreter.
+            Err(e) if e.starts_with("cannot run") => eprintln!("skipped: {}", e),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn python_and_rust_are_compared() {
+        // No part is solved in both languages yet: a script standing in for
+        // 2024/17 part 1 prints an answer, which the Rust solution checks.
+        let root = env::temp_dir().join(format!("aoc-python-rust-{}", std::process::id()));
+        let dir = root.join("2024/day17_chronospatial_computer");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.dev"), "").unwrap();
+        let runs: Vec<PythonRun> = ["4,2,5,6,7,7,7,7,3,1,0", "4,2,5"]
+            .into_iter()
+            .map(|answer| {
+                fs::write(dir.join("part1.py"), format!("print('{}')\n", answer)).unwrap();
+                let days = python_days(&root, 2024).unwrap();
+                let mut run = run_day(&root, &days[0], &[1], "dev", false).remove(0);
+                run.rust = rust_run(&run);
+                run
+            })
+            .collect();
+        fs::remove_dir_all(&root).unwrap();
+        if let Err(e) = &runs[0].answer {
+            // Nothing to compare without an interpreter.
+            assert!(e.starts_with("cannot run"), "{}", e);
+            eprintln!("skipped: {}", e);
+            return;
+        }
+        assert_eq!(runs[0].agrees(), Some(true));
+        assert_eq!(runs[1].agrees(), Some(false));
+        assert_eq!(runs[1].to_json()["rust_answer"], "4,2,5,6,7,7,7,7,3,1,0");
+    }
+}
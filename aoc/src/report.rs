@@ -0,0 +1,46 @@
+// JSON reports of the commands, for scripts and dashboards.
+
+use aoc_common::memory::Usage;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+
+// Writes the report to the standard output for `-`, to a file otherwise.
+pub fn write(target: &str, report: &Value) -> Result<(), String> {
+    let report = serde_json::to_string_pretty(report).unwrap();
+    if target == "-" {
+        println!("{}", report);
+        Ok(())
+    } else {
+        fs::write(target, report + "\n").map_err(|e| format!("{}: {}", target, e))
+    }
+}
+
+// Identifies an input independently of where it was read from.
+pub fn digest(input: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+// The memory figures of a run, null where they are unknown.
+pub fn memory(usage: Option<&Usage>) -> Value {
+    let heap = usage.and_then(|usage| usage.heap);
+    json!({
+        "peak_heap_bytes": heap.map(|heap| heap.peak),
+        "allocations": heap.map(|heap| heap.allocations),
+        "allocated_bytes": heap.map(|heap| heap.allocated),
+        "peak_resident_bytes": usage.and_then(|usage| usage.peak_resident),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digests_are_sha256() {
+        assert_eq!(
+            digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,476 @@
+// Where every year stands: a calendar of the days with their stars, the
+// languages they are solved in and when they last ran, followed by the parts
+// that need attention.
+//
+// A part is verified (a star) when its `prod` answer is known, from
+// `answers.toml` or from a correct submission in `attempts.toml`, unverified
+// when it is solved without a known answer, and failing when its last run
+// (kept by the commands running parts, see `aoc::history`) failed or
+// disagreed with a known answer. Parts without a Rust or Python solution are
+// missing.
+
+use crate::client;
+use crate::python::{self, PythonDay};
+use crate::report;
+use aoc::attempts::{Attempts, Verdict};
+use aoc::days;
+use aoc::history::{self, History};
+use aoc::known::{self, KnownDay};
+use aoc::root_dir;
+use aoc_common::answers::Answers;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Verified,
+    Unverified,
+    Failing,
+    Missing,
+}
+
+impl State {
+    fn as_str(self) -> &'static str {
+        match self {
+            State::Verified => "verified",
+            State::Unverified => "unverified",
+            State::Failing => "failing",
+            State::Missing => "missing",
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            State::Verified => '*',
+            State::Unverified => '+',
+            State::Failing => '!',
+            State::Missing => '.',
+        }
+    }
+}
+
+const LEGEND: &str = "* verified  + unverified  ! failing  . missing  R rust  P python";
+
+#[derive(Debug)]
+struct PartStatus {
+    part: u8,
+    state: State,
+    solvers: Vec<&'static str>,
+    detail: Option<String>,
+}
+
+#[derive(Debug)]
+struct DayStatus {
+    year: u16,
+    day: u8,
+    title: String,
+    rust: bool,
+    python: bool,
+    parts: Vec<PartStatus>,
+    // When a part of the day last ran, in seconds since the Unix epoch.
+    last_run: Option<u64>,
+}
+
+impl DayStatus {
+    fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| p.state == State::Verified)
+            .count()
+    }
+
+    fn languages(&self) -> String {
+        [(self.rust, 'R'), (self.python, 'P')]
+            .iter()
+            .filter(|(solved, _)| *solved)
+            .map(|(_, language)| language)
+            .collect()
+    }
+
+    fn to_json(&self) -> Value {
+        let parts: Vec<Value> = self
+            .parts
+            .iter()
+            .map(|p| {
+                json!({
+                    "part": p.part,
+                    "state": p.state.as_str(),
+                    "solvers": p.solvers,
+                    "detail": p.detail,
+                })
+            })
+            .collect();
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "title": self.title,
+            "rust": self.rust,
+            "python": self.python,
+            "last_run": self.last_run,
+            "parts": parts,
+        })
+    }
+}
+
+// The last day has a single puzzle, its second star is for all the others.
+fn parts_of(day: u8) -> Vec<u8> {
+    if day == 25 {
+        vec![1]
+    } else {
+        vec![1, 2]
+    }
+}
+
+fn part_status(
+    (year, day): (u16, u8),
+    part: u8,
+    solvers: Vec<&'static str>,
+    answers: &Answers,
+    attempts: &Attempts,
+    history: &History,
+) -> PartStatus {
+    let correct = attempts
+        .of_part(part)
+        .find(|a| a.verdict == Verdict::Correct)
+        .map(|a| a.answer.as_str());
+    let expected = |input: &str| {
+        answers
+            .get(input, part)
+            .or(correct.filter(|_| input == "prod"))
+    };
+    let failures: Vec<String> = history
+        .of_part(year, day, part)
+        .filter(|run| solvers.contains(&run.solver.as_str()))
+        .filter_map(|run| match (&run.answer, expected(&run.input)) {
+            (Ok(answer), Some(expected)) if answer != expected => Some(format!(
+                "{} answered {} on {}, expected {}",
+                run.solver, answer, run.input, expected
+            )),
+            (Err(e), expected) if expected.is_some() || run.input == "prod" => {
+                Some(format!("{} failed on {}: {}", run.solver, run.input, e))
+            }
+            _ => None,
+        })
+        .collect();
+    let (state, detail) = if solvers.is_empty() {
+        (State::Missing, None)
+    } else if !failures.is_empty() {
+        (State::Failing, Some(failures.join("; ")))
+    } else if expected("prod").is_some() {
+        (State::Verified, None)
+    } else {
+        (State::Unverified, None)
+    };
+    PartStatus {
+        part,
+        state,
+        solvers,
+        detail,
+    }
+}
+
+fn day_status(
+    root: &Path,
+    year: u16,
+    (day, name): &(u8, String),
+    known: &[KnownDay],
+    python: Option<&PythonDay>,
+    history: &History,
+) -> Result<DayStatus, String> {
+    let dir = root.join(year.to_string()).join(name);
+    let rust = days::find(year, *day);
+    let known = known.iter().find(|k| (k.year, k.day) == (year, *day));
+    let title = match (rust, known, python) {
+        (Some(rust), _, _) => rust.title.to_string(),
+        (None, Some(known), _) => known.title.clone(),
+        (None, None, Some(python)) => python.title.clone(),
+        (None, None, None) => python::title_of(name),
+    };
+    let answers = Answers::load(&dir).map_err(|e| e.to_string())?;
+    let attempts = Attempts::load(&dir).map_err(|e| e.to_string())?;
+    let mut status = DayStatus {
+        year,
+        day: *day,
+        title,
+        rust: rust.is_some() || dir.join("Cargo.toml").is_file(),
+        python: python.is_some(),
+        parts: Vec::new(),
+        last_run: None,
+    };
+    for part in parts_of(*day) {
+        let mut solvers = Vec::new();
+        if rust.is_some_and(|rust| rust.part(part).is_some()) {
+            solvers.push("rust");
+        }
+        if python.is_some_and(|python| python.has_part(part)) {
+            solvers.push("python");
+        }
+        let part = part_status((year, *day), part, solvers, &answers, &attempts, history);
+        status.parts.push(part);
+    }
+    status.last_run = parts_of(*day)
+        .into_iter()
+        .flat_map(|part| history.of_part(year, *day, part))
+        .map(|run| run.time)
+        .max();
+    Ok(status)
+}
+
+// `3h ago` for a run three hours before `now`.
+fn ago(time: u64, now: u64) -> String {
+    let seconds = now.saturating_sub(time);
+    match seconds {
+        0..60 => "now".to_string(),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+// Day of the week of the first of December, from 0 for Monday.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, counting from Sunday.
+    let y = year as usize;
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + 4 + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+const CELL_WIDTH: usize = 8;
+
+// The days of December up to Christmas, a week per row, with two lines for
+// every day: its stars and languages, then when it last ran.
+fn calendar(year: u16, days: &[DayStatus], now: u64) -> String {
+    let cells: Vec<[String; 2]> = (1..=25)
+        .map(|day| match days.iter().find(|d| d.day == day) {
+            Some(status) => {
+                let stars: String = status.parts.iter().map(|p| p.state.symbol()).collect();
+                [
+                    format!("{:>2} {:<2} {}", day, stars, status.languages()),
+                    status
+                        .last_run
+                        .map(|time| format!("{:>w$}", ago(time, now), w = CELL_WIDTH))
+                        .unwrap_or_default(),
+                ]
+            }
+            None => [format!("{:>2}", day), String::new()],
+        })
+        .collect();
+    let blank = [String::new(), String::new()];
+    let offset = first_weekday(year);
+    let slots: Vec<&[String; 2]> = std::iter::repeat_n(&blank, offset)
+        .chain(cells.iter())
+        .collect();
+
+    let header = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        .map(|name| format!("{:<w$}", name, w = CELL_WIDTH))
+        .join("  ");
+    let mut lines = vec![header.trim_end().to_string()];
+    for week in slots.chunks(7) {
+        for line in 0..2 {
+            let row: Vec<String> = week
+                .iter()
+                .map(|cell| format!("{:<w$}", cell[line], w = CELL_WIDTH))
+                .collect();
+            lines.push(row.join("  ").trim_end().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+fn years(root: &Path) -> Result<Vec<u16>, String> {
+    let entries = fs::read_dir(root).map_err(|e| format!("{}: {}", root.display(), e))?;
+    let mut years: Vec<u16> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            (name.len() == 4).then(|| name.parse().ok()).flatten()
+        })
+        .collect();
+    years.sort();
+    Ok(years)
+}
+
+fn print_year(year: u16, days: &[DayStatus], now: u64) {
+    let count = |state: State| {
+        days.iter()
+            .flat_map(|d| d.parts.iter())
+            .filter(|p| p.state == state)
+            .count()
+    };
+    println!(
+        "{}: {} stars, {} unverified, {} failing, {} missing",
+        year,
+        count(State::Verified),
+        count(State::Unverified),
+        count(State::Failing),
+        count(State::Missing)
+    );
+    println!("{}", calendar(year, days, now));
+    for day in days {
+        for part in day.parts.iter().filter(|p| p.state != State::Verified) {
+            let solvers = part.solvers.join(", ");
+            let line = format!(
+                "  {}/{:02} part {}  {:<10}  {:<12}  {}",
+                year,
+                day.day,
+                part.part,
+                part.state.as_str(),
+                solvers,
+                part.detail.as_deref().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
+        }
+    }
+    println!();
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut year = None;
+    let mut json = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = Some(args.next().ok_or("missing json file")?),
+            _ if year.is_none() => {
+                year = Some(arg.parse::<u16>().map_err(|_| "year must be a number")?)
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let root = root_dir();
+    let years = match year {
+        Some(year) => vec![year],
+        None => years(&root)?,
+    };
+    let known = known::load(&root).map_err(|e| e.to_string())?;
+    let history = History::load(root.join(client::STATE_DIR)).map_err(|e| e.to_string())?;
+    let now = history::now();
+    let mut reports = Vec::new();
+    let mut failing = false;
+    for year in years {
+        let python_days = python::python_days(&root, year)?;
+        let days = python::day_dirs(&root, year)?
+            .iter()
+            .map(|dir| {
+                let python = python_days.iter().find(|p| p.day == dir.0);
+                day_status(&root, year, dir, &known, python, &history)
+            })
+            .collect::<Result<Vec<DayStatus>, String>>()?;
+        failing |= days
+            .iter()
+            .flat_map(|d| d.parts.iter())
+            .any(|p| p.state == State::Failing);
+        if json.as_deref() != Some("-") {
+            print_year(year, &days, now);
+        }
+        let stars: usize = days.iter().map(DayStatus::stars).sum();
+        let days: Vec<Value> = days.iter().map(DayStatus::to_json).collect();
+        reports.push(json!({ "year": year, "stars": stars, "days": days }));
+    }
+    if let Some(json) = &json {
+        report::write(json, &json!({ "years": reports }))?;
+    } else {
+        println!("{}", LEGEND);
+    }
+    Ok(!failing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u8, states: [State; 2], last_run: Option<u64>) -> DayStatus {
+        DayStatus {
+            year: 2022,
+            day,
+            title: String::new(),
+            rust: true,
+            python: day.is_multiple_of(2),
+            parts: states
+                .into_iter()
+                .zip(1..)
+                .map(|(state, part)| PartStatus {
+                    part,
+                    state,
+                    solvers: vec!["rust"],
+                    detail: None,
+                })
+                .collect(),
+            last_run,
+        }
+    }
+
+    #[test]
+    fn december_starts_on_the_right_day() {
+        // Thursday, Friday and Sunday
+        assert_eq!(first_weekday(2022), 3);
+        assert_eq!(first_weekday(2023), 4);
+        assert_eq!(first_weekday(2024), 6);
+    }
+
+    #[test]
+    fn calendar_shows_stars_languages_and_runs() {
+        let days = [
+            day(1, [State::Verified, State::Verified], Some(10000)),
+            day(2, [State::Unverified, State::Missing], None),
+            day(4, [State::Failing, State::Verified], Some(10000 - 7200)),
+        ];
+        let calendar = calendar(2022, &days, 10000);
+        let lines: Vec<&str> = calendar.split('\n').collect();
+        assert_eq!(lines.len(), 1 + 4 * 2);
+        assert_eq!(
+            lines[1],
+            format!("{:30} 1 ** R    2 +. RP   3         4 !* RP", "")
+        );
+        assert_eq!(lines[2], format!("{:30}     now{:22}  2h ago", "", ""));
+        assert_eq!(
+            lines[3],
+            " 5         6         7         8         9        10        11"
+        );
+    }
+
+    #[test]
+    fn failing_parts_come_from_the_last_runs() {
+        let mut answers = Answers::default();
+        answers.set("prod", 1, "42".to_string());
+        let mut history = History::default();
+        let check = |history: &History| {
+            part_status(
+                (2022, 1),
+                1,
+                vec!["rust"],
+                &answers,
+                &Attempts::default(),
+                history,
+            )
+        };
+        assert_eq!(check(&history).state, State::Verified);
+        history.record(history::LastRun {
+            year: 2022,
+            day: 1,
+            part: 1,
+            solver: "rust".to_string(),
+            input: "prod".to_string(),
+            answer: Ok("41".to_string()),
+            elapsed: None,
+            time: 0,
+        });
+        let failing = check(&history);
+        assert_eq!(failing.state, State::Failing);
+        assert_eq!(
+            failing.detail.as_deref(),
+            Some("rust answered 41 on prod, expected 42")
+        );
+        let unsolved = part_status(
+            (2022, 1),
+            2,
+            vec![],
+            &answers,
+            &Attempts::default(),
+            &history,
+        );
+        assert_eq!(unsolved.state, State::Missing);
+    }
+}
@@ -0,0 +1,336 @@
+// Submits the answer of a part and tells what the site thinks of it.
+//
+// Without an answer on the command line the registered solution is run on
+// the `prod` input. Judged answers are recorded in `attempts.toml` of the
+// day (see `attempts.rs`), which is consulted before anything is sent:
+// answers of solved parts and answers rejected before are never sent again,
+// and numeric answers beyond one known to be too high or too low are only
+// sent with `--force`. Correct answers also go to `answers.toml`.
+
+use crate::client::{Client, Config};
+use crate::Run;
+use aoc::attempts::{Attempt, Attempts, Check, Verdict};
+use aoc::days;
+use aoc::known::{self, KnownDay};
+use aoc::root_dir;
+use aoc_common::answers::Answers;
+use std::error::Error;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// What the site answered to a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Response {
+    Judged(Verdict),
+    // Answers can only be sent so often, the wait time is given if the page
+    // tells it.
+    RateLimited(Option<Duration>),
+    // The part is locked or solved already.
+    WrongLevel,
+    Unknown(String),
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let year: u16 = args
+        .next()
+        .ok_or("missing year")?
+        .parse()
+        .map_err(|_| "year must be a number")?;
+    let day: u8 = args
+        .next()
+        .ok_or("missing day")?
+        .parse()
+        .map_err(|_| "day must be a number")?;
+    let part: u8 = match args.next().as_deref() {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ => return Err("part must be 1 or 2".into()),
+    };
+    let mut answer = None;
+    let mut force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            _ if answer.is_none() && !arg.starts_with("--") => answer = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let root = root_dir();
+    let known = known::load(&root).map_err(|e| e.to_string())?;
+    let known_day = known
+        .into_iter()
+        .find(|k| (k.year, k.day) == (year, day))
+        .ok_or_else(|| format!("{}/{} is not in {}", year, day, known::KNOWN_DAYS_FILE))?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = days::find(year, day)
+                .ok_or_else(|| format!("{}/{} is not registered, give the answer", year, day))?;
+            Run::new(day, part, "prod").answer?
+        }
+    };
+    let config = Config::load(&root).map_err(|e| e.to_string())?;
+
+    let prefix = format!("{}/{} part {}", year, day, part);
+    match submit(&root, &known_day, part, &answer, force, &config) {
+        Ok(Response::Judged(Verdict::Correct)) => {
+            println!("{}: {} is correct", prefix, answer);
+            Ok(true)
+        }
+        Ok(Response::Judged(verdict)) => {
+            println!("{}: {} is {}", prefix, answer, verdict);
+            Ok(false)
+        }
+        Ok(Response::RateLimited(wait)) => {
+            match wait {
+                Some(wait) => println!("{}: rate limited, try again in {:?}", prefix, wait),
+                None => println!("{}: rate limited, try again later", prefix),
+            }
+            Ok(false)
+        }
+        Ok(Response::WrongLevel) => {
+            println!("{}: the part is locked or already solved", prefix);
+            Ok(false)
+        }
+        Ok(Response::Unknown(text)) => {
+            println!("{}: unexpected response: {}", prefix, text);
+            Ok(false)
+        }
+        Err(e) => {
+            eprintln!("{}: {}", prefix, e);
+            Ok(false)
+        }
+    }
+}
+
+// Sends `answer` unless the attempts so far rule it out, and records the
+// verdict.
+fn submit(
+    root: &Path,
+    day: &KnownDay,
+    part: u8,
+    answer: &str,
+    force: bool,
+    config: &Config,
+) -> Result<Response, Box<dyn Error>> {
+    let day_dir = root.join(&day.dir);
+    let mut attempts = Attempts::load(&day_dir)?;
+    match attempts.check(part, answer) {
+        Check::Plausible => {}
+        Check::Solved(attempt) => {
+            return Err(format!("already solved with {}", attempt.answer).into())
+        }
+        Check::Repeated(attempt) => {
+            return Err(format!("{} was already submitted: {}", answer, attempt.verdict).into())
+        }
+        Check::OutOfBounds(attempt) if !force => {
+            return Err(format!(
+                "{} cannot be right, {} was {} (use --force to send it anyway)",
+                answer, attempt.answer, attempt.verdict
+            )
+            .into())
+        }
+        Check::OutOfBounds(attempt) => eprintln!(
+            "warning: sending {} although {} was {}",
+            answer, attempt.answer, attempt.verdict
+        ),
+    }
+
+    let client = Client::new(config.clone())?;
+    let level = part.to_string();
+    let page = client.post(
+        &format!("{}/day/{}/answer", day.year, day.day),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let response = parse_response(&page);
+    if let Response::Judged(verdict) = response {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let attempt = Attempt {
+            part,
+            answer: answer.to_string(),
+            verdict,
+            time,
+        };
+        attempts.record(&day_dir, attempt)?;
+        if verdict == Verdict::Correct {
+            let mut answers = Answers::load(&day_dir)?;
+            if answers.get("prod", part).is_none() {
+                answers.set("prod", part, answer.to_string());
+                answers.save(&day_dir)?;
+            }
+        }
+    }
+    Ok(response)
+}
+
+// Interprets the page the site answers a submission with, from the text of
+// its `<article>`.
+fn parse_response(page: &str) -> Response {
+    let text = article_text(page);
+    if text.contains("That's the right answer") {
+        Response::Judged(Verdict::Correct)
+    } else if text.contains("That's not the right answer") {
+        Response::Judged(if text.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Response::RateLimited(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        Response::Unknown(text)
+    }
+}
+
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The wait time of "You have 1m 4s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (value, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock;
+    use std::fs;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><main>\n<article><p>{}</p></article>\n</main></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn responses_are_parsed() {
+        let cases = [
+            ("That's the right answer! You are <em>one gold star</em> closer.", Response::Judged(Verdict::Correct)),
+            ("That's not the right answer; your answer is too high. Please wait one minute.", Response::Judged(Verdict::TooHigh)),
+            ("That's not the right answer; your answer is too low.", Response::Judged(Verdict::TooLow)),
+            ("That's not the right answer. If you're stuck, ...", Response::Judged(Verdict::Wrong)),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait.",
+                Response::RateLimited(Some(Duration::from_secs(64))),
+            ),
+            ("You don't seem to be solving the right level.  Did you already complete it?", Response::WrongLevel),
+            ("Something <b>else</b>", Response::Unknown("Something else".to_string())),
+        ];
+        for (text, response) in cases {
+            assert_eq!(parse_response(&page(text)), response, "{}", text);
+        }
+    }
+
+    #[test]
+    fn submissions_are_remembered() {
+        let server = mock::Server::start(|request| {
+            let text = match request.body.as_str() {
+                "level=1&answer=500" => "That's not the right answer; your answer is too high.",
+                "level=1&answer=5" => "That's not the right answer; your answer is too low.",
+                "level=1&answer=42" => "That's the right answer!",
+                _ => "That's not the right answer.",
+            };
+            (200, page(text))
+        });
+        let root = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let day = KnownDay {
+            year: 2022,
+            day: 3,
+            title: "Test".to_string(),
+            dir: "2022/day3_test".to_string(),
+            package: "aoc2022_day3".to_string(),
+        };
+        let day_dir = root.join(&day.dir);
+        fs::create_dir_all(&day_dir).unwrap();
+        let config = server.config(&root);
+        let submit = |answer: &str, force: bool| submit(&root, &day, 1, answer, force, &config);
+
+        assert_eq!(
+            submit("500", false).unwrap(),
+            Response::Judged(Verdict::TooHigh)
+        );
+        assert!(submit("500", false).is_err());
+        assert!(submit("600", false).is_err());
+        assert_eq!(
+            submit("600", true).unwrap(),
+            Response::Judged(Verdict::Wrong)
+        );
+        assert_eq!(
+            submit("5", false).unwrap(),
+            Response::Judged(Verdict::TooLow)
+        );
+        assert_eq!(
+            submit("42", false).unwrap(),
+            Response::Judged(Verdict::Correct)
+        );
+        assert!(submit("43", false).is_err());
+
+        let requests = server.received(4);
+        let bodies: Vec<&str> = requests.iter().map(|r| r.body.as_str()).collect();
+        assert_eq!(
+            bodies,
+            [
+                "level=1&answer=500",
+                "level=1&answer=600",
+                "level=1&answer=5",
+                "level=1&answer=42"
+            ]
+        );
+        assert!(requests
+            .iter()
+            .all(|r| r.method == "POST" && r.url == "/2022/day/3/answer"));
+        let attempts = Attempts::load(&day_dir).unwrap();
+        let recorded: Vec<_> = attempts.of_part(1).map(|a| a.verdict).collect();
+        assert_eq!(
+            recorded,
+            [
+                Verdict::TooHigh,
+                Verdict::Wrong,
+                Verdict::TooLow,
+                Verdict::Correct
+            ]
+        );
+        assert_eq!(Answers::load(&day_dir).unwrap().get("prod", 1), Some("42"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,377 @@
+// Re-runs a day whenever its sources or its input change: the day's `src/`
+// and `common/src/`, its `Cargo.toml`, the selected input and `answers.toml`
+// are polled, and on every change the runner is rebuilt and the selected
+// parts run again. Their answers are shown with the time they took, against
+// the answers recorded for the input in `answers.toml`. Parts without one
+// are checked against the answer given with `--expect` while they are being
+// worked on: it is kept in the state directory (`.aoc/expected/2022-16.toml`,
+// in the same format), not in `answers.toml`, which only holds verified
+// answers. Stops with Ctrl-C.
+//
+// Days that are not registered in the runner yet, like the ones just created
+// by `aoc new`, are found in `days.toml` or by their directory. Their own
+// binary is rebuilt and run instead, and its output shown as it is.
+//
+// The runner is built in a target directory of its own (`target/watch`), so
+// a rebuild never replaces the binary that is watching.
+
+use crate::args::{self, Selection};
+use crate::{client, python};
+use aoc::{days, known, root_dir};
+use aoc_common::answers::Answers;
+use aoc_common::input::InputSource;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const TARGET_DIR: &str = "target/watch";
+const EXPECTED_DIR: &str = "expected";
+
+// Where the answers expected with `--expect` for a day are kept.
+fn expected_file(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(client::STATE_DIR)
+        .join(EXPECTED_DIR)
+        .join(format!("{}-{:02}.toml", year, day))
+}
+
+// Modification times of the watched files that exist.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn snapshot(files: &[PathBuf], dirs: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut add = |path: PathBuf| {
+        if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+            snapshot.insert(path, modified);
+        }
+    };
+    let mut pending = dirs.to_vec();
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => pending.push(entry.path()),
+                Ok(_) => add(entry.path()),
+                Err(_) => {}
+            }
+        }
+    }
+    files.iter().cloned().for_each(add);
+    snapshot
+}
+
+// The files that differ between two snapshots, appeared or gone included.
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|p| !after.contains_key(*p)).cloned());
+    changed
+}
+
+// How `answer` differs from `expected`, line by line for answers spanning
+// several lines: `-` for the expected lines, `+` for the actual ones.
+fn diff(expected: &str, answer: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.trim_matches('\n').lines().collect();
+    let answer: Vec<&str> = answer.trim_matches('\n').lines().collect();
+    if expected == answer {
+        return Vec::new();
+    }
+    if expected.len() <= 1 && answer.len() <= 1 {
+        return vec![format!("expected {}", expected.first().unwrap_or(&""))];
+    }
+    let mut lines = Vec::new();
+    for i in 0..expected.len().max(answer.len()) {
+        match (expected.get(i), answer.get(i)) {
+            (Some(e), Some(a)) if e == a => lines.push(format!("  {}", a)),
+            (e, a) => {
+                lines.extend(e.map(|e| format!("- {}", e)));
+                lines.extend(a.map(|a| format!("+ {}", a)));
+            }
+        }
+    }
+    lines
+}
+
+// How the watched day is run.
+#[derive(Debug, PartialEq, Eq)]
+enum Solver {
+    // The day is registered in the runner, which checks the answers.
+    Runner,
+    // The binary of the day crate with this package name.
+    Package(String),
+}
+
+// The directory of a day, relative to `root`, and how to run it.
+fn find_day(root: &Path, year: u16, day: u8) -> Result<(String, Solver), String> {
+    if let Some(found) = days::find(year, day) {
+        return Ok((found.dir.to_string(), Solver::Runner));
+    }
+    let known = known::load(root).map_err(|e| e.to_string())?;
+    if let Some(found) = known.iter().find(|k| (k.year, k.day) == (year, day)) {
+        return Ok((found.dir.clone(), Solver::Package(found.package.clone())));
+    }
+    let (_, name) = python::day_dirs(root, year)?
+        .into_iter()
+        .find(|(d, _)| *d == day)
+        .ok_or_else(|| format!("{}/{} has no directory", year, day))?;
+    let dir = format!("{}/{}", year, name);
+    let manifest_path = root.join(&dir).join("Cargo.toml");
+    let manifest: toml::Table = fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| content.parse().map_err(|e: toml::de::Error| e.to_string()))
+        .map_err(|e| format!("{}: {}", manifest_path.display(), e))?;
+    let package = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(toml::Value::as_str)
+        .ok_or_else(|| format!("{}: missing package name", manifest_path.display()))?;
+    Ok((dir, Solver::Package(package.to_string())))
+}
+
+fn build(root: &Path, package: &str) -> Result<(), String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let built = Command::new(&cargo)
+        .args(["build", "--quiet", "--release", "--package", package])
+        .arg("--target-dir")
+        .arg(root.join(TARGET_DIR))
+        .current_dir(root)
+        .status()
+        .map_err(|e| format!("cannot run {}: {}", cargo, e))?;
+    if !built.success() {
+        return Err("build failed".into());
+    }
+    Ok(())
+}
+
+// Rebuilds the day and runs the selected parts.
+fn run(
+    root: &Path,
+    selection: &Selection,
+    day: u8,
+    day_dir: &Path,
+    solver: &Solver,
+) -> Result<(), String> {
+    if let Solver::Package(package) = solver {
+        build(root, package)?;
+        // Both parts, each answer on a line of its own for crates made from
+        // the template.
+        let status = Command::new(root.join(TARGET_DIR).join("release").join(package))
+            .arg(&selection.input)
+            .status()
+            .map_err(|e| format!("cannot run {}: {}", package, e))?;
+        if !status.success() {
+            return Err(format!("{} failed ({})", package, status));
+        }
+        return Ok(());
+    }
+    build(root, "aoc")?;
+
+    let mut runner = Command::new(root.join(TARGET_DIR).join("release/aoc"));
+    runner.args([selection.year.to_string(), day.to_string()]);
+    if let Some(part) = selection.part {
+        runner.args(["--part", &part.to_string()]);
+    }
+    runner.args(["--input", &selection.input, "--json", "-"]);
+    // Errors of the parts go to the standard error as they happen.
+    let output = runner
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("cannot run aoc: {}", e))?;
+    let report: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|_| format!("aoc failed ({})", output.status))?;
+
+    let answers = Answers::load(day_dir).map_err(|e| e.to_string())?;
+    let expectations =
+        Answers::load_file(expected_file(root, selection.year, day)).map_err(|e| e.to_string())?;
+    for result in report["results"].as_array().into_iter().flatten() {
+        let part = result["part"].as_u64().unwrap_or_default() as u8;
+        let elapsed = result["wall_time_ns"].as_u64().map(Duration::from_nanos);
+        let expected = answers
+            .get(&selection.input, part)
+            .or_else(|| expectations.get(&selection.input, part));
+        let Some(answer) = result["answer"].as_str() else {
+            if let Some(expected) = expected {
+                println!("part {}: failed, expected {}", part, expected);
+            }
+            continue;
+        };
+        let diffs = expected.map(|expected| diff(expected, answer));
+        let verdict = match &diffs {
+            None => "no expected answer".to_string(),
+            Some(lines) if lines.is_empty() => "ok".to_string(),
+            Some(lines) if lines.len() == 1 => lines[0].clone(),
+            Some(_) => "differs".to_string(),
+        };
+        let time = format!("{:.2?}", elapsed.unwrap_or_default());
+        if !answer.contains('\n') {
+            println!("part {}: {} ({}) [{}]", part, answer, verdict, time);
+            continue;
+        }
+        println!("part {}: ({}) [{}]", part, verdict, time);
+        let lines = match diffs {
+            Some(lines) if lines.len() > 1 => lines,
+            _ => answer.lines().map(|line| format!("  {}", line)).collect(),
+        };
+        lines.iter().for_each(|line| println!("  {}", line));
+    }
+    Ok(())
+}
+
+pub fn main(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut selection = Selection::new(args.next(), args.next())?;
+    let day = selection.day.ok_or("watch needs a day, not a whole year")?;
+    let mut interval = Duration::from_millis(500);
+    let mut expect = None;
+    while let Some(arg) = args.next() {
+        if selection.option(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--interval" => interval = Duration::from_millis(args::number(&arg, args.next())?),
+            "--expect" => expect = Some(args.next().ok_or("missing expected answer")?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    if selection.input == "-" {
+        return Err("the standard input cannot be watched".into());
+    }
+
+    let root = root_dir();
+    let (dir, solver) = find_day(&root, selection.year, day)?;
+    let day_dir = root.join(dir);
+    if solver != Solver::Runner {
+        println!(
+            "{}/{} is not registered in the runner: its binary is run and its answers are not checked",
+            selection.year, day
+        );
+    }
+    let expected = expected_file(&root, selection.year, day);
+    if let Some(expect) = expect {
+        let part = selection.part.ok_or("--expect needs --part")?;
+        let mut expectations = Answers::load_file(&expected).map_err(|e| e.to_string())?;
+        expectations.set(&selection.input, part, expect);
+        let saved = expected
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| expectations.save_file(&expected));
+        saved.map_err(|e| format!("{}: {}", expected.display(), e))?;
+    }
+    let mut files = vec![
+        day_dir.join("Cargo.toml"),
+        day_dir.join(aoc_common::answers::ANSWERS_FILE),
+        expected,
+    ];
+    if let InputSource::File(input) = InputSource::resolve(&selection.input, &day_dir) {
+        files.push(input);
+    }
+    // Every day depends on the shared helpers.
+    let dirs = [day_dir.join("src"), root.join("common/src")];
+
+    let mut seen = snapshot(&files, &dirs);
+    loop {
+        if let Err(e) = run(&root, &selection, day, &day_dir, &solver) {
+            eprintln!("{}", e);
+        }
+        println!("watching {} for changes", day_dir.display());
+        let changed = loop {
+            thread::sleep(interval);
+            let now = snapshot(&files, &dirs);
+            let changed = changes(&seen, &now);
+            seen = now;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&day_dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        println!("\n{} changed", names.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_diffed_by_line() {
+        assert!(diff("42", "42").is_empty());
+        assert_eq!(diff("42", "41"), ["expected 42"]);
+        assert!(diff("\n#.\n.#", "#.\n.#").is_empty());
+        assert_eq!(
+            diff("#.\n.#\n##", "#.\n##"),
+            ["  #.", "- .#", "+ ##", "- ##"]
+        );
+    }
+
+    #[test]
+    fn unregistered_days_are_found() {
+        let (dir, solver) = find_day(&root_dir(), 2022, 1).unwrap();
+        assert_eq!(
+            (dir.as_str(), solver),
+            ("2022/day1_calorie_counting", Solver::Runner)
+        );
+
+        let root = env::temp_dir().join(format!("aoc-watch-days-{}", std::process::id()));
+        fs::create_dir_all(root.join("2030/day2_scaffolded")).unwrap();
+        fs::write(
+            root.join(known::KNOWN_DAYS_FILE),
+            "[[day]]\nyear = 2030\nday = 1\ntitle = \"New\"\ndir = \"2030/day1_new\"\npackage = \"aoc2030_day1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("2030/day2_scaffolded/Cargo.toml"),
+            "[package]\nname = \"aoc2030_day2\"\n",
+        )
+        .unwrap();
+        let found: Vec<_> = (1..=3).map(|day| find_day(&root, 2030, day)).collect();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(
+            found[0],
+            Ok((
+                "2030/day1_new".to_string(),
+                Solver::Package("aoc2030_day1".to_string())
+            ))
+        );
+        assert_eq!(
+            found[1],
+            Ok((
+                "2030/day2_scaffolded".to_string(),
+                Solver::Package("aoc2030_day2".to_string())
+            ))
+        );
+        assert_eq!(found[2], Err("2030/3 has no directory".to_string()));
+    }
+
+    #[test]
+    fn changes_are_found_between_snapshots() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let (files, dirs) = ([dir.join("input.dev")], [dir.join("src")]);
+        let before = snapshot(&files, &dirs);
+        fs::write(&files[0], "1").unwrap();
+        fs::write(dir.join("src/bin/extra.rs"), "").unwrap();
+        let after = snapshot(&files, &dirs);
+        fs::remove_dir_all(&dir).unwrap();
+        let mut changed = changes(&before, &after);
+        changed.sort();
+        assert_eq!(
+            changed,
+            [dir.join("input.dev"), dir.join("src/bin/extra.rs")]
+        );
+        assert!(changes(&after, &after).is_empty());
+    }
+}
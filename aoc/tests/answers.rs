@@ -0,0 +1,55 @@
+// Runs every registered part against each input listed in the day's
+// `answers.toml` and compares the result with the recorded answer.
+
+use aoc::days::DAYS;
+use aoc::root_dir;
+use aoc_common::answers::Answers;
+use aoc_common::input::InputSource;
+
+#[test]
+fn solved_parts_match_known_answers() {
+    let mut failures = Vec::new();
+    for day in DAYS {
+        let day_dir = root_dir().join(day.dir);
+        let answers = Answers::load(&day_dir).unwrap();
+        for variant in answers.variants() {
+            for part in [1, 2] {
+                let (Some(solve), Some(expected)) = (day.part(part), answers.get(variant, part))
+                else {
+                    continue;
+                };
+                let name = format!("{}/{} part {} [{}]", day.year, day.day, part, variant);
+                let result = InputSource::resolve(variant, &day_dir)
+                    .open()
+                    .map_err(Into::into)
+                    .and_then(|mut input| solve(&mut input));
+                match result {
+                    Ok(answer) if answer == expected => {}
+                    Ok(answer) => {
+                        failures.push(format!("{}: expected {}, got {}", name, expected, answer))
+                    }
+                    Err(e) => failures.push(format!("{}: {}", name, e)),
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn solved_parts_have_a_prod_answer() {
+    let mut missing = Vec::new();
+    for day in DAYS {
+        let answers = Answers::load(root_dir().join(day.dir)).unwrap();
+        for part in [1, 2] {
+            if day.part(part).is_some() && answers.get("prod", part).is_none() {
+                missing.push(format!("{}/{} part {}", day.year, day.day, part));
+            }
+        }
+    }
+    assert!(
+        missing.is_empty(),
+        "no prod answer for: {}",
+        missing.join(", ")
+    );
+}
@@ -0,0 +1,151 @@
+// Known puzzle answers of a day, kept in an `answers.toml` file next to its
+// inputs. Every table is named after the input selector (see `InputSource`)
+// the answers belong to:
+//
+//     [prod]
+//     part1 = "67658"
+//     part2 = "200158"
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    variants: BTreeMap<String, [Option<String>; 2]>,
+}
+
+impl Answers {
+    // Loads the answers of the day in `day_dir`. A missing file means that
+    // no answer is known yet.
+    pub fn load<P: AsRef<Path>>(day_dir: P) -> Result<Self, Box<dyn Error>> {
+        Self::load_file(day_dir.as_ref().join(ANSWERS_FILE))
+    }
+
+    // Loads answers kept in the same format elsewhere than in a day.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|e| format!("{}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, day_dir: P) -> io::Result<()> {
+        self.save_file(day_dir.as_ref().join(ANSWERS_FILE))
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, variant: &str, part: u8) -> Option<&str> {
+        let answers = self.variants.get(variant)?;
+        answers.get(part_index(part)?)?.as_deref()
+    }
+
+    pub fn set(&mut self, variant: &str, part: u8, answer: String) {
+        let i = part_index(part).expect("part must be 1 or 2");
+        self.variants.entry(variant.to_string()).or_default()[i] = Some(answer);
+    }
+
+    pub fn variants(&self) -> impl Iterator<Item = &str> {
+        self.variants.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+}
+
+fn part_index(part: u8) -> Option<usize> {
+    match part {
+        1 | 2 => Some(part as usize - 1),
+        _ => None,
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let mut answers = Answers::default();
+        for (variant, parts) in table {
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("[{}] must be a table", variant))?;
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(format!("[{}] unknown key '{}'", variant, key).into()),
+                };
+                // Numeric answers may be written without quotes.
+                let answer = match value {
+                    toml::Value::String(v) => v.clone(),
+                    toml::Value::Integer(v) => v.to_string(),
+                    _ => return Err(format!("[{}] {} must be a string", variant, key).into()),
+                };
+                answers.set(&variant, part, answer);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = toml::Table::new();
+        for (variant, parts) in &self.variants {
+            let mut answers = toml::Table::new();
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    answers.insert(format!("part{}", i + 1), answer.clone().into());
+                }
+            }
+            table.insert(variant.clone(), answers.into());
+        }
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_print_round_trip() {
+        let answers: Answers = "[prod]\npart1 = \"67658\"\npart2 = 200158\n\n[\"input2.dev\"]\npart2 = '''\n#.\n.#'''\n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get("prod", 1), Some("67658"));
+        assert_eq!(answers.get("prod", 2), Some("200158"));
+        assert_eq!(answers.get("input2.dev", 1), None);
+        assert_eq!(answers.get("input2.dev", 2), Some("#.\n.#"));
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!("[prod]\npart3 = \"1\"\n".parse::<Answers>().is_err());
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn answers_round_trip() {
+        crate::fuzz::round_trips::<Answers>(&[
+            "[prod]\npart1 = \"67658\"\npart2 = \"200158\"\n",
+            "[dev]\npart1 = 24000\n\n[\"input2.dev\"]\npart2 = \"#.\\n.#\"\n",
+        ]);
+    }
+}
@@ -0,0 +1,142 @@
+// Detection of cycles in deterministic processes, to tell what happens after
+// far more steps than could be simulated.
+//
+// The process is a state advanced one step at a time by a step function. A
+// key identifies the state: once a key repeats, so do all following steps.
+// Keys need only the parts of the state that decide how it evolves (e.g. the
+// shape of the top of a tower rather than the whole tower).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    // Number of steps before the state enters the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest step with the same state as after `steps` steps.
+    pub fn equivalent_step(&self, steps: u64) -> usize {
+        let (start, length) = (self.start as u64, self.length as u64);
+        if steps < start {
+            return steps as usize;
+        }
+        (start + (steps - start) % length) as usize
+    }
+}
+
+// A cycle together with a metric (e.g. height) of the state after every step
+// until the cycle closed, for extrapolating the metric.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedCycle {
+    pub cycle: Cycle,
+    metrics: Vec<i64>,
+}
+
+impl TrackedCycle {
+    // The metric after `steps` steps, assuming it grows by the same amount on
+    // every round of the cycle.
+    pub fn metric_at(&self, steps: u64) -> i64 {
+        let Cycle { start, length } = self.cycle;
+        if steps < self.metrics.len() as u64 {
+            return self.metrics[steps as usize];
+        }
+        let rounds = (steps - start as u64) / length as u64;
+        let per_round = self.metrics[start + length] - self.metrics[start];
+        self.metrics[self.cycle.equivalent_step(steps)] + rounds as i64 * per_round
+    }
+}
+
+// Advances `state` with `step` until it gets back to a state seen before, at
+// most `max_steps` times. `step` returns false when the process is over, in
+// which case there is no cycle either. The state is left where the cycle
+// closed (or the process ended).
+pub fn find_cycle<S, K, F, G>(state: &mut S, max_steps: usize, step: F, key: G) -> Option<Cycle>
+where
+    K: Hash + Eq,
+    F: FnMut(&mut S) -> bool,
+    G: FnMut(&S) -> K,
+{
+    track_cycle(state, max_steps, step, key, |_| 0).map(|tracked| tracked.cycle)
+}
+
+// Like `find_cycle`, also recording `metric` of the state after every step.
+pub fn track_cycle<S, K, F, G, M>(
+    state: &mut S,
+    max_steps: usize,
+    mut step: F,
+    mut key: G,
+    mut metric: M,
+) -> Option<TrackedCycle>
+where
+    K: Hash + Eq,
+    F: FnMut(&mut S) -> bool,
+    G: FnMut(&S) -> K,
+    M: FnMut(&S) -> i64,
+{
+    let mut seen = HashMap::new();
+    let mut metrics = Vec::new();
+    for steps in 0..=max_steps {
+        metrics.push(metric(state));
+        if let Some(start) = seen.insert(key(state), steps) {
+            let cycle = Cycle {
+                start,
+                length: steps - start,
+            };
+            return Some(TrackedCycle { cycle, metrics });
+        }
+        if steps == max_steps || !step(state) {
+            break;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_are_found_and_extrapolated() {
+        // 0, 1, 2, 3, 4, 2, 3, 4, ... while the total keeps growing.
+        let step = |(x, total): &mut (u32, i64)| {
+            *x = if *x == 4 { 2 } else { *x + 1 };
+            *total += *x as i64;
+            true
+        };
+        let mut state = (0, 0);
+        let tracked = track_cycle(&mut state, 100, step, |s| s.0, |s| s.1).unwrap();
+        assert_eq!((tracked.cycle.start, tracked.cycle.length), (2, 3));
+        assert_eq!(state, (2, 12));
+
+        let mut state = (0, 0);
+        let mut expected = vec![0];
+        for _ in 0..30 {
+            step(&mut state);
+            expected.push(state.1);
+        }
+        for (steps, total) in expected.into_iter().enumerate() {
+            assert_eq!(tracked.metric_at(steps as u64), total, "{}", steps);
+        }
+        assert_eq!(tracked.metric_at(1_000_000_000_000), 2_999_999_999_998);
+        assert_eq!(tracked.cycle.equivalent_step(1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn ending_processes_have_no_cycle() {
+        let mut x = 0;
+        let step = |x: &mut u32| {
+            *x += 1;
+            *x < 10
+        };
+        assert_eq!(find_cycle(&mut x, 100, step, |x| *x), None);
+        assert_eq!(x, 10);
+        let count = |x: &mut u32| {
+            *x += 1;
+            true
+        };
+        assert_eq!(find_cycle(&mut 0, 5, count, |x| *x), None);
+    }
+}
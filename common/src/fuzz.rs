@@ -0,0 +1,204 @@
+// Property tests for input parsers, enabled by the `fuzz` feature (day crates
+// turn it on for their tests only).
+//
+// Parsers are fed arbitrary text as well as valid samples (typically lines of
+// the example input) with a few random edits: characters deleted, inserted,
+// replaced or repeated, and the text cut short. Mutated samples get much
+// deeper into a parser than random text does. Whatever comes in, a parser
+// must return a value or an error, never panic.
+//
+// The number of cases can be raised with `PROPTEST_CASES`, and a failure
+// reports the smallest input found to trigger it.
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestRunner};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+// Characters that mean something to some parser, plus a few multi-byte ones
+// to catch slicing in the middle of a character.
+const ALPHABET: &str = "0123456789-+*/=,.:;[]() \nabcxyzABXYZé€";
+
+// Positions are taken modulo the length of the text.
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    // Repeats the characters of a range of the text.
+    Repeat(usize, usize),
+    Truncate(usize),
+}
+
+fn interesting_char() -> impl Strategy<Value = char> {
+    let alphabet: Vec<char> = ALPHABET.chars().collect();
+    prop_oneof![4 => proptest::sample::select(alphabet), 1 => any::<char>()]
+}
+
+fn position() -> impl Strategy<Value = usize> {
+    0..4096usize
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        position().prop_map(Edit::Delete),
+        (position(), interesting_char()).prop_map(|(i, c)| Edit::Insert(i, c)),
+        (position(), interesting_char()).prop_map(|(i, c)| Edit::Replace(i, c)),
+        (position(), position()).prop_map(|(a, b)| Edit::Repeat(a, b)),
+        position().prop_map(Edit::Truncate),
+    ]
+}
+
+fn apply(text: &str, edits: &[Edit]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for edit in edits {
+        let len = chars.len();
+        match edit {
+            Edit::Insert(i, c) => chars.insert(i % (len + 1), *c),
+            _ if len == 0 => {}
+            Edit::Delete(i) => {
+                chars.remove(i % len);
+            }
+            Edit::Replace(i, c) => chars[i % len] = *c,
+            Edit::Repeat(a, b) => {
+                let (a, b) = (a % len, b % len);
+                let range = a.min(b)..a.max(b) + 1;
+                let repeated: Vec<char> = chars[range.clone()].to_vec();
+                chars.splice(range.end..range.end, repeated);
+            }
+            Edit::Truncate(i) => chars.truncate(i % len),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// Text made of characters parsers care about, and now and then any other.
+pub fn text() -> BoxedStrategy<String> {
+    proptest::collection::vec(interesting_char(), 0..64)
+        .prop_map(|chars| chars.into_iter().collect())
+        .boxed()
+}
+
+// One of `samples` with one to four random edits.
+pub fn mutated(samples: &[&str]) -> BoxedStrategy<String> {
+    let samples: Vec<String> = samples.iter().map(|s| s.to_string()).collect();
+    (
+        proptest::sample::select(samples),
+        proptest::collection::vec(edit(), 1..5),
+    )
+        .prop_map(|(sample, edits)| apply(&sample, &edits))
+        .boxed()
+}
+
+// Mostly mutated samples, some arbitrary text.
+pub fn inputs(samples: &[&str]) -> BoxedStrategy<String> {
+    prop_oneof![3 => mutated(samples), 1 => text()].boxed()
+}
+
+// The non-empty lines of `text`, to use an example input as samples.
+pub fn lines(text: &str) -> Vec<&str> {
+    text.lines().filter(|line| !line.is_empty()).collect()
+}
+
+// The blank-line separated records of `text`, for parsers of multi-line
+// records.
+pub fn records(text: &str) -> Vec<&str> {
+    text.split("\n\n")
+        .map(|record| record.trim_matches('\n'))
+        .filter(|record| !record.is_empty())
+        .collect()
+}
+
+// Runs `property` on inputs from `strategy`, panicking with the smallest
+// failing input if it panics (or fails an assertion) on any of them.
+pub fn check<S, F>(strategy: S, property: F)
+where
+    S: Strategy,
+    S::Value: Debug,
+    F: Fn(S::Value),
+{
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let result = TestRunner::new(config).run(&strategy, |value| {
+        property(value);
+        Ok(())
+    });
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+}
+
+// Checks that parsing inputs derived from `samples` as `T` never panics,
+// neither does formatting the errors. Every sample must parse.
+pub fn parses<T>(samples: &[&str])
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    for sample in samples {
+        if let Err(e) = sample.parse::<T>() {
+            panic!("sample {:?} does not parse: {}", sample, e);
+        }
+    }
+    check(inputs(samples), |input| {
+        if let Err(e) = input.parse::<T>() {
+            let _ = e.to_string();
+        }
+    });
+}
+
+// Like `parses`, also checking that whatever parses prints as text that
+// parses back to the same value.
+pub fn round_trips<T>(samples: &[&str])
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Display,
+{
+    parses::<T>(samples);
+    check(inputs(samples), |input| {
+        if let Ok(value) = input.parse::<T>() {
+            let printed = value.to_string();
+            match printed.parse::<T>() {
+                Ok(reparsed) => assert_eq!(reparsed, value, "{:?} printed as {:?}", input, printed),
+                Err(e) => panic!("{:?} printed as {:?}, which fails: {}", input, printed, e),
+            }
+        }
+    });
+}
+
+// Checks that `parse` never panics on a whole input, derived from `example`
+// which must be accepted.
+pub fn parses_input<F, E>(example: &str, parse: F)
+where
+    F: Fn(&[u8]) -> Result<(), E>,
+    E: Display,
+{
+    if let Err(e) = parse(example.as_bytes()) {
+        panic!("example does not parse: {}", e);
+    }
+    check(inputs(&[example]), |input| {
+        if let Err(e) = parse(input.as_bytes()) {
+            let _ = e.to_string();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_wrap_around_the_text() {
+        assert_eq!(apply("abc", &[Edit::Delete(4)]), "ac");
+        assert_eq!(apply("", &[Edit::Delete(0), Edit::Insert(9, 'x')]), "x");
+        let edits = [Edit::Repeat(0, 1), Edit::Replace(0, 'é')];
+        assert_eq!(apply("a1b", &edits), "é1a1b");
+    }
+
+    #[test]
+    fn numbers_round_trip() {
+        round_trips::<i64>(&["12", "-7", "0"]);
+    }
+}
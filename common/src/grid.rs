@@ -0,0 +1,327 @@
+// Rectangular grid of cells, as found in most of the map-like puzzles.
+//
+// Positions are `(x, y)` pairs: `x` is the column and `y` the row, with
+// `(0, 0)` in the top-left corner of the input.
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::Point2;
+use crate::input::{lines, InputError};
+
+pub type Pos = (usize, usize);
+
+// Offsets of the four orthogonal neighbours: up, right, down, left.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+// Offsets of all eight neighbours, clockwise starting from up.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "row {} has {} cells, expected {}",
+                    y,
+                    row.len(),
+                    width
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    // Reads a grid with one row per line, converting every character with
+    // `parse_cell`. Blank lines are skipped.
+    pub fn parse<R, F, E>(input: R, mut parse_cell: F) -> Result<Self, InputError>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Result<T, E>,
+        E: Into<Box<dyn Error>>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in lines(input) {
+            let (n, line) = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let cells_before = cells.len();
+            for c in line.chars() {
+                cells.push(parse_cell(c).map_err(|e| InputError::parse(n, e))?);
+            }
+            let row_width = cells.len() - cells_before;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(InputError::parse(
+                        n,
+                        format!("row has {} cells, expected {}", row_width, width),
+                    ))
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Converts signed coordinates into a position, if it lies on the grid.
+    pub fn pos(&self, x: i64, y: i64) -> Option<Pos> {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+
+    pub fn point_pos(&self, point: Point2) -> Option<Pos> {
+        self.pos(point.x, point.y)
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let offset = self.offset(pos);
+        Some(&mut self.cells[offset])
+    }
+
+    // Replaces the cell at `pos`, returns false when it is out of bounds.
+    pub fn set(&mut self, pos: Pos, value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn offset(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    // Moves `pos` by the offset `(dx, dy)`, staying on the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        self.pos(x as i64 + dx as i64, y as i64 + dy as i64)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    // Positions reached by repeatedly moving by `delta` from `pos` (which is
+    // not included), up to the edge of the grid. E.g. `(1, 1)` walks the
+    // diagonal towards the bottom-right corner.
+    pub fn ray(&self, pos: Pos, delta: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let mut current = Some(pos);
+        std::iter::from_fn(move || {
+            current = self.step(current?, delta);
+            current
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy with an empty grid.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        &self.cells[self.offset(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars<R: BufRead>(input: R) -> Result<Self, InputError> {
+        Grid::parse(input, Ok::<char, Infallible>)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_chars(s.as_bytes())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_print() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let err = "abc\nde\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(err.line(), 2);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = grid();
+        let n4: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(n4, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn rays_rows_and_columns() {
+        let grid = grid();
+        let ray: Vec<char> = grid.ray((0, 0), (1, 1)).map(|p| grid[p]).collect();
+        assert_eq!(ray, vec!['e']);
+        let ray: Vec<Pos> = grid.ray((2, 0), (-1, 0)).collect();
+        assert_eq!(ray, vec![(1, 0), (0, 0)]);
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn grids_round_trip() {
+        crate::fuzz::round_trips::<Grid<char>>(&["30373\n25512\n65332", "Sabqponm\nabcryxxl", "#"]);
+    }
+}
@@ -0,0 +1,385 @@
+// Line and record oriented reading of puzzle inputs.
+//
+// Every error coming out of this module carries the (1-based) number of the
+// input line it was raised on, so a malformed input can be located quickly.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::parse::ParseError;
+
+// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    // Resolves an input selector against the directory of a day:
+    //  - `-` reads the standard input,
+    //  - a selector containing a path separator is used as a path as is,
+    //  - a file of that name in the day's directory is used (e.g. `input2.dev`),
+    //  - anything else names a variant, e.g. `dev` for `input.dev`.
+    pub fn resolve<P: AsRef<Path>>(selector: &str, day_dir: P) -> Self {
+        let day_dir = day_dir.as_ref();
+        if selector == "-" {
+            return InputSource::Stdin;
+        }
+        let path = Path::new(selector);
+        if path.is_absolute() || path.components().count() > 1 {
+            return InputSource::File(path.to_path_buf());
+        }
+        let file = day_dir.join(selector);
+        if file.is_file() {
+            return InputSource::File(file);
+        }
+        InputSource::File(day_dir.join(format!("input.{}", selector)))
+    }
+
+    // Resolves the selector given as the first command line argument,
+    // or `default` when there is none.
+    pub fn from_args<P: AsRef<Path>>(day_dir: P, default: &str) -> Self {
+        let selector = env::args().nth(1).unwrap_or_else(|| default.to_string());
+        Self::resolve(&selector, day_dir)
+    }
+
+    // Opens the input for reading. It can be opened any number of times,
+    // the standard input is read once and kept in memory.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        static STDIN: OnceLock<Vec<u8>> = OnceLock::new();
+
+        match self {
+            InputSource::Stdin => {
+                if STDIN.get().is_none() {
+                    let mut content = Vec::new();
+                    io::stdin().lock().read_to_end(&mut content)?;
+                    let _ = STDIN.set(content);
+                }
+                Ok(Box::new(STDIN.get().unwrap().as_slice()))
+            }
+            InputSource::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(e) => Err(io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", path.display(), e),
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "-"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub enum InputError {
+    Io { line: usize, source: io::Error },
+    Parse { line: usize, source: Box<dyn Error> },
+}
+
+impl InputError {
+    pub fn parse<E: Into<Box<dyn Error>>>(line: usize, source: E) -> Self {
+        InputError::Parse {
+            line,
+            source: source.into(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            InputError::Io { line, .. } | InputError::Parse { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io { line, source } => {
+                write!(f, "line {}: cannot read input: {}", line, source)
+            }
+            InputError::Parse { line, source } => match source.downcast_ref::<ParseError>() {
+                Some(e) => e.fmt_at(f, *line),
+                None => write!(f, "line {}: {}", line, source),
+            },
+        }
+    }
+}
+
+// Shows the diagnostic rather than the structure, so that it reads well when
+// returned from `main`.
+impl fmt::Debug for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            InputError::Parse { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+pub struct Lines<R> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<(usize, String), InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line += 1;
+        Some(
+            line.map(|l| (self.line, l))
+                .map_err(|source| InputError::Io {
+                    line: self.line,
+                    source,
+                }),
+        )
+    }
+}
+
+// Iterates over the input lines together with their line numbers.
+pub fn lines<R: BufRead>(input: R) -> Lines<R> {
+    Lines {
+        lines: input.lines(),
+        line: 0,
+    }
+}
+
+// Converts every line into an event and hands it over to `process_event`.
+// Errors returned by either closure are reported with the current line number.
+pub fn process<R, F, G, Event>(
+    input: R,
+    mut process_line: F,
+    mut process_event: G,
+) -> Result<(), InputError>
+where
+    R: BufRead,
+    F: FnMut(String) -> Result<Event, Box<dyn Error>>,
+    G: FnMut(Event) -> Result<(), Box<dyn Error>>,
+{
+    for line in lines(input) {
+        let (n, l) = line?;
+        process_line(l)
+            .and_then(&mut process_event)
+            .map_err(|e| InputError::parse(n, e))?;
+    }
+    Ok(())
+}
+
+// Parses every line of the input into `T`.
+pub fn parse_lines<T, R>(input: R) -> Result<Vec<T>, InputError>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: Into<Box<dyn Error>>,
+{
+    lines(input)
+        .map(|line| {
+            let (n, l) = line?;
+            l.parse().map_err(|e| InputError::parse(n, e))
+        })
+        .collect()
+}
+
+// A group of consecutive non-blank lines.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Record {
+    // Number of the first line of the record.
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+
+impl Record {
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        (self.line..).zip(self.lines.iter().map(String::as_str))
+    }
+
+    // Parses the whole record, reporting errors on its first line.
+    pub fn parse<T>(&self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error>>,
+    {
+        self.text()
+            .parse()
+            .map_err(|e| InputError::parse(self.line, e))
+    }
+}
+
+pub struct Records<R> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Record, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<Record> = None;
+        for line in self.lines.by_ref() {
+            let (n, l) = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if l.trim().is_empty() {
+                if record.is_some() {
+                    break;
+                }
+                continue;
+            }
+            record
+                .get_or_insert_with(|| Record {
+                    line: n,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(l);
+        }
+        record.map(Ok)
+    }
+}
+
+// Iterates over the blank-line separated records of the input. Any number of
+// blank lines may separate two records and the last one needs no terminator.
+pub fn records<R: BufRead>(input: R) -> Records<R> {
+    Records {
+        lines: lines(input),
+    }
+}
+
+// Parses every record of the input into `T`.
+pub fn parse_records<T, R>(input: R) -> Result<Vec<T>, InputError>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: Into<Box<dyn Error>>,
+{
+    records(input).map(|record| record?.parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Source;
+
+    #[test]
+    fn input_source_selectors() {
+        let day_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(InputSource::resolve("-", day_dir), InputSource::Stdin);
+        assert_eq!(
+            InputSource::resolve("dev", day_dir),
+            InputSource::File(day_dir.join("input.dev"))
+        );
+        assert_eq!(
+            InputSource::resolve("Cargo.toml", day_dir),
+            InputSource::File(day_dir.join("Cargo.toml"))
+        );
+        assert_eq!(
+            InputSource::resolve("../input.prod", day_dir),
+            InputSource::File(PathBuf::from("../input.prod"))
+        );
+    }
+
+    #[test]
+    fn records_are_split_on_blank_lines() {
+        let input = "\n1\n2\n\n\n3\n\n4";
+        let records: Vec<Record> = records(input.as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    line: 2,
+                    lines: vec!["1".to_string(), "2".to_string()]
+                },
+                Record {
+                    line: 6,
+                    lines: vec!["3".to_string()]
+                },
+                Record {
+                    line: 8,
+                    lines: vec!["4".to_string()]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_report_line_number() {
+        let err = parse_lines::<u32, _>("1\n2\nthree\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert_eq!(err.to_string(), "line 3: invalid digit found in string");
+    }
+
+    #[test]
+    fn io_errors_report_line_number() {
+        let input: &[u8] = b"1\n\xff\n";
+        let err = parse_lines::<u32, _>(input).unwrap_err();
+        assert!(matches!(err, InputError::Io { line: 2, .. }));
+    }
+
+    #[test]
+    fn parse_errors_are_located_in_records() {
+        struct Numbers;
+
+        impl FromStr for Numbers {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let source = Source::new(s);
+                for number in s.split_whitespace() {
+                    source.parse::<u32>(number)?;
+                }
+                Ok(Numbers)
+            }
+        }
+
+        let Err(err) = parse_records::<Numbers, _>("1 2\n\n3\n4 -5".as_bytes()) else {
+            panic!("-5 is not a number");
+        };
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 3: invalid '-5': invalid digit found in string\n  |\n4 | 4 -5\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn process_reports_event_errors() {
+        let mut sum = 0;
+        let err = process(
+            "1\n2\n3".as_bytes(),
+            |line| Ok(line.parse::<u32>()?),
+            |v| {
+                if v == 3 {
+                    return Err("three is not allowed".into());
+                }
+                sum += v;
+                Ok(())
+            },
+        )
+        .unwrap_err();
+        assert_eq!(sum, 3);
+        assert_eq!(err.to_string(), "line 3: three is not allowed");
+    }
+}
@@ -0,0 +1,16 @@
+// Building blocks shared by the puzzle solutions of all years.
+
+pub mod answers;
+pub mod cycle;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod parse;
+pub mod random;
+pub mod ranges;
+pub mod render;
+pub mod search;
+pub mod trace;
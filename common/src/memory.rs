@@ -0,0 +1,230 @@
+// Memory use of the parts: the heap through an allocator counting the
+// allocations, and the resident memory of the process as the system sees it.
+//
+// A binary counts its allocations once it installs the allocator:
+//
+//     aoc_common::count_allocations!();
+//
+// Day binaries report what each part used on the standard error when
+// `AOC_PROFILE` is set, e.g. `AOC_PROFILE=1 cargo run --release`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+// The system allocator, counting what goes through it.
+pub struct Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::freed(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Installs `Counting` as the global allocator of the binary.
+#[macro_export]
+macro_rules! count_allocations {
+    () => {
+        #[global_allocator]
+        static ALLOCATOR: $crate::memory::Counting = $crate::memory::Counting;
+    };
+}
+
+// What the heap went through while measuring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heap {
+    pub allocations: u64,
+    // Bytes allocated in total, freed or not.
+    pub allocated: u64,
+    // Most bytes allocated at once, on top of what was allocated before.
+    pub peak: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    // Unknown when the binary does not count its allocations.
+    pub heap: Option<Heap>,
+    // Most resident bytes of the process, where the system tells it.
+    pub peak_resident: Option<u64>,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(heap) = self.heap {
+            parts.push(format!(
+                "peak heap {}, {} allocations ({})",
+                Bytes(heap.peak),
+                heap.allocations,
+                Bytes(heap.allocated)
+            ));
+        }
+        if let Some(resident) = self.peak_resident {
+            parts.push(format!("peak resident {}", Bytes(resident)));
+        }
+        if parts.is_empty() {
+            write!(f, "no memory figures")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+// A number of bytes, printed in binary units.
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let units = ["B", "KiB", "MiB", "GiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < units.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} B", self.0),
+            _ => write!(f, "{:.1} {}", value, units[unit]),
+        }
+    }
+}
+
+// Runs `f`, returning what it used.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let counting = ALLOCATIONS.load(Ordering::Relaxed) > 0;
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    reset_peak_resident();
+
+    let value = f();
+
+    let heap = counting.then(|| Heap {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    });
+    let usage = Usage {
+        heap,
+        peak_resident: peak_resident(),
+    };
+    (value, usage)
+}
+
+// Runs `f`, and reports what it used as `label` when `AOC_PROFILE` is set.
+pub fn profile<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    if env::var_os(PROFILE_VAR).is_none_or(|v| v.is_empty()) {
+        return f();
+    }
+    let (value, usage) = measure(f);
+    eprintln!("{}: {}", label, usage);
+    value
+}
+
+// The high water mark of the resident memory, from `/proc` on Linux.
+fn peak_resident() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kib: u64 = line["VmHWM:".len()..]
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+// Brings the high water mark down to the current resident memory, where the
+// system allows it (Linux 4.0 on).
+fn reset_peak_resident() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::count_allocations!();
+
+    #[test]
+    fn allocations_are_counted() {
+        let (buffer, usage) = measure(|| vec![1u8; 1 << 20]);
+        let heap = usage.heap.unwrap();
+        // other tests may allocate at the same time
+        assert!(heap.allocations >= 1);
+        assert!(heap.allocated >= 1 << 20 && heap.peak >= 1 << 20);
+        drop(buffer);
+    }
+
+    #[test]
+    fn bytes_are_printed_in_binary_units() {
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 << 30).to_string(), "3.0 GiB");
+    }
+
+    #[test]
+    fn usage_is_printed() {
+        let usage = Usage {
+            heap: Some(Heap {
+                allocations: 12,
+                allocated: 4096,
+                peak: 2048,
+            }),
+            peak_resident: None,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "peak heap 2.0 KiB, 12 allocations (4.0 KiB)"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// Diagnostics for malformed puzzle input.
+//
+// A `ParseError` tells what is wrong with the parsed text and, when known,
+// where: the offending part of it, reported as a line and column along with
+// the line itself. `Source` wraps the text being parsed so that errors about
+// any part of it can be located, also when they come from parsing a part
+// with another `FromStr`.
+//
+// When the text is one line (or record) of the input, `InputError::parse`
+// shifts the position to the line of the input.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    span: Option<Span>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Span {
+    text: String,
+    range: Range<usize>,
+}
+
+impl ParseError {
+    pub fn new<M: fmt::Display>(message: M) -> Self {
+        Self {
+            message: message.to_string(),
+            span: None,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // Line (counting from 1) of the offending text within the parsed text.
+    pub fn line(&self) -> Option<usize> {
+        let span = self.span.as_ref()?;
+        Some(span.text[..span.range.start].matches('\n').count() + 1)
+    }
+
+    // Column (counting characters from 1) of the offending text.
+    pub fn column(&self) -> Option<usize> {
+        let span = self.span.as_ref()?;
+        let before = &span.text[..span.range.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(before[line_start..].chars().count() + 1)
+    }
+
+    // The offending text.
+    pub fn snippet(&self) -> Option<&str> {
+        let span = self.span.as_ref()?;
+        Some(&span.text[span.range.clone()])
+    }
+
+    // Writes the error as found on line `first_line + line - 1` of a file.
+    pub(crate) fn fmt_at(&self, f: &mut fmt::Formatter, first_line: usize) -> fmt::Result {
+        let (Some(span), Some(line), Some(column)) = (&self.span, self.line(), self.column())
+        else {
+            return write!(f, "line {}: {}", first_line, self.message);
+        };
+        let line = first_line + line - 1;
+        write!(f, "line {}, column {}: {}", line, column, self.message)?;
+        span.fmt_snippet(f, &line.to_string(), column)
+    }
+}
+
+impl Span {
+    fn fmt_snippet(&self, f: &mut fmt::Formatter, line: &str, column: usize) -> fmt::Result {
+        let line_start = self.text[..self.range.start]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let line_end = self.text[line_start..]
+            .find('\n')
+            .map_or(self.text.len(), |i| line_start + i);
+        let underline = self.text[self.range.start..self.range.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = " ".repeat(line.len());
+        write!(
+            f,
+            "\n{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            line,
+            &self.text[line_start..line_end],
+            gutter,
+            " ".repeat(column - 1),
+            "^".repeat(underline)
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.span, self.line(), self.column()) {
+            (Some(span), Some(1), Some(column)) if !span.text.contains('\n') => {
+                write!(f, "column {}: {}", column, self.message)?;
+                span.fmt_snippet(f, "", column)
+            }
+            (Some(_), Some(_), Some(_)) => self.fmt_at(f, 1),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+// Shows the diagnostic rather than the structure, so that it reads well when
+// returned from `main`.
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    // An error about `part`, which should be a slice of the source text. If
+    // it is not, the first occurrence of it is blamed instead.
+    pub fn error<M: fmt::Display>(&self, part: &str, message: M) -> ParseError {
+        ParseError {
+            message: message.to_string(),
+            span: self.range_of(part).map(|range| Span {
+                text: self.text.to_string(),
+                range,
+            }),
+        }
+    }
+
+    fn range_of(&self, part: &str) -> Option<Range<usize>> {
+        let text = self.text.as_ptr() as usize;
+        let start = (part.as_ptr() as usize).wrapping_sub(text);
+        if start <= self.text.len() && start + part.len() <= self.text.len() {
+            return Some(start..start + part.len());
+        }
+        let start = self.text.find(part)?;
+        Some(start..start + part.len())
+    }
+
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, format!("expected '{}'", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, format!("expected '{}'", prefix)))
+    }
+
+    // Parses `part` with its `FromStr`. Errors located within `part` are
+    // moved to their place in the source text; any other error blames the
+    // whole of `part`.
+    pub fn parse<T>(&self, part: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error>>,
+    {
+        part.parse().map_err(|e: T::Err| {
+            let e: Box<dyn Error> = e.into();
+            match (e.downcast::<ParseError>(), self.range_of(part)) {
+                (Ok(e), Some(range)) => match e.span {
+                    Some(inner) if inner.text == part => ParseError {
+                        message: e.message,
+                        span: Some(Span {
+                            text: self.text.to_string(),
+                            range: range.start + inner.range.start..range.start + inner.range.end,
+                        }),
+                    },
+                    _ => self.error(part, e.message),
+                },
+                (Ok(e), None) => *e,
+                (Err(e), _) => self.error(part, format!("invalid '{}': {}", part, e)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Pair(u32, u32);
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let source = Source::new(s);
+            let (a, b) = source.split_once(s, ",")?;
+            Ok(Pair(source.parse(a)?, source.parse(b)?))
+        }
+    }
+
+    #[test]
+    fn errors_are_located() {
+        let Pair(a, b) = "1,2".parse().unwrap();
+        assert_eq!((a, b), (1, 2));
+        let err = "12,x4".parse::<Pair>().unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(1), Some(4)));
+        assert_eq!(err.snippet(), Some("x4"));
+        assert_eq!(
+            err.to_string(),
+            "column 4: invalid 'x4': invalid digit found in string\n |\n | 12,x4\n |    ^^"
+        );
+        let err = "12".parse::<Pair>().unwrap_err();
+        assert_eq!(err.column(), Some(1));
+        assert_eq!(err.message(), "expected ','");
+    }
+
+    #[test]
+    fn nested_errors_are_moved_into_place() {
+        let text = "pairs\n1,2 3,y";
+        let source = Source::new(text);
+        let (_, pairs) = source.split_once(text, "\n").unwrap();
+        let err = pairs
+            .split(' ')
+            .map(|pair| source.parse::<Pair>(pair))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(7)));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 7: invalid 'y': invalid digit found in string\n  |\n2 | 1,2 3,y\n  |       ^"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// A small seeded pseudo-random number generator (SplitMix64) for the input
+// generators of the days. Its sequences are fixed by the algorithm, so a seed
+// always gives the same input, whatever the platform or crate versions.
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "no number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // A number in `min..=max`.
+    pub fn between(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "empty range {}..={}", min, max);
+        // Up to 2^64 numbers, for the whole range of `i64`.
+        let span = max.abs_diff(min) as u128 + 1;
+        min.wrapping_add_unsigned(((self.next_u64() as u128 * span) >> 64) as u64)
+    }
+
+    // True `percent` times out of a hundred.
+    pub fn chance(&mut self, percent: u32) -> bool {
+        self.below(100) < percent as usize
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_depend_on_the_seed_only() {
+        // The first output of SplitMix64 seeded with 0.
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.between(-3, 3)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| numbers(7).contains(&n)));
+    }
+
+    #[test]
+    fn ranges_may_span_all_numbers() {
+        let mut rng = Rng::new(7);
+        let numbers: Vec<i64> = (0..100).map(|_| rng.between(i64::MIN, i64::MAX)).collect();
+        assert!(numbers.iter().any(|&n| n < 0) && numbers.iter().any(|&n| n > 0));
+        assert_eq!(rng.between(i64::MAX, i64::MAX), i64::MAX);
+        assert_eq!(rng.between(i64::MIN, i64::MIN), i64::MIN);
+        assert!((0..100).all(|_| rng.between(i64::MAX - 1, i64::MAX) >= i64::MAX - 1));
+    }
+}
@@ -0,0 +1,282 @@
+// Sets of integers kept as sorted, disjoint ranges, and tables mapping ranges
+// of integers by an offset.
+//
+// Ranges are half-open (`start..end`) internally. Inclusive ranges, as most
+// puzzles describe them, are accepted wherever a range is expected.
+
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+
+// A range of integers, either `start..end` or `start..=end`.
+pub trait Interval {
+    fn half_open(self) -> Range<i64>;
+}
+
+impl Interval for Range<i64> {
+    fn half_open(self) -> Range<i64> {
+        self
+    }
+}
+
+impl Interval for RangeInclusive<i64> {
+    fn half_open(self) -> Range<i64> {
+        *self.start()..*self.end() + 1
+    }
+}
+
+// Invariant: the ranges are non-empty, sorted, and neither overlap nor touch,
+// so every set has exactly one representation.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<I: Interval>(&mut self, range: I) {
+        let range = range.half_open();
+        if range.is_empty() {
+            return;
+        }
+        // First range that ends at or after the start, i.e. touches or
+        // overlaps `range` or comes after it.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first == last {
+            range
+        } else {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<i64>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn is_superset(&self, other: &RangeSet) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    pub fn overlaps(&self, other: &RangeSet) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let range = x.start.max(y.start)..x.end.min(y.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        // Pieces of disjoint ranges cannot touch, no merging needed.
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(o) = others.peek() {
+                if o.end <= start {
+                    others.next();
+                    continue;
+                }
+                if o.start >= range.end {
+                    break;
+                }
+                if o.start > start {
+                    ranges.push(start..o.start);
+                }
+                start = o.end;
+                if o.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    // The integers of `within` that are not in the set.
+    pub fn gaps<I: Interval>(&self, within: I) -> RangeSet {
+        RangeSet::from(within).difference(self)
+    }
+}
+
+impl<I: Interval> From<I> for RangeSet {
+    fn from(range: I) -> Self {
+        let mut set = RangeSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<I: Interval> FromIterator<I> for RangeSet {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut ranges: Vec<Range<i64>> = iter
+            .into_iter()
+            .map(Interval::half_open)
+            .filter(|r| !r.is_empty())
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+}
+
+impl<I: Interval> Extend<I> for RangeSet {
+    fn extend<T: IntoIterator<Item = I>>(&mut self, iter: T) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl fmt::Debug for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.ranges.iter()).finish()
+    }
+}
+
+// Moves the integers of every source range by the offset given for it and
+// leaves all others as they are. When source ranges overlap, the one added
+// first applies.
+#[derive(Debug, Clone, Default)]
+pub struct OffsetMap {
+    entries: Vec<(Range<i64>, i64)>,
+}
+
+impl OffsetMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<I: Interval>(&mut self, source: I, offset: i64) {
+        self.entries.push((source.half_open(), offset));
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        self.entries
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    // Image of all integers of `set`.
+    pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+        let mut unmapped = set.clone();
+        let mut mapped = Vec::new();
+        for (source, offset) in &self.entries {
+            let hit = unmapped.intersection(&RangeSet::from(source.clone()));
+            mapped.extend(hit.iter().map(|r| r.start + offset..r.end + offset));
+            unmapped = unmapped.difference(&hit);
+        }
+        mapped.into_iter().chain(unmapped.iter()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> RangeSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn ranges_are_merged() {
+        let mut s = set(&[5..7, 0..2, 1..3, 10..10]);
+        assert_eq!(s.ranges(), &[0..3, 5..7]);
+        s.insert(3..=4);
+        assert_eq!(s, RangeSet::from(0..7));
+        s.insert(9..12);
+        s.insert(-4..-2);
+        assert_eq!(s.ranges(), &[-4..-2, 0..7, 9..12]);
+        s.insert(-3..10);
+        assert_eq!(s, RangeSet::from(-4..12));
+        assert_eq!(s.len(), 16);
+        assert_eq!((s.min(), s.max()), (Some(-4), Some(11)));
+        assert!(s.contains(-4) && s.contains(11) && !s.contains(12));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 28..29, 40..50]);
+        assert_eq!(a.union(&b).ranges(), &[0..30, 40..50]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..29]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28, 29..30]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 40..50]);
+        assert_eq!(a.gaps(-5..=35).ranges(), &[-5..0, 10..20, 30..36]);
+        assert!(a.is_superset(&set(&[1..3, 22..30])));
+        assert!(!a.is_superset(&RangeSet::from(9..11)));
+        assert!(a.overlaps(&b) && !a.overlaps(&RangeSet::from(10..20)));
+    }
+
+    #[test]
+    fn offsets_map_ranges() {
+        let mut map = OffsetMap::new();
+        map.insert(98..=99, 50 - 98);
+        map.insert(50..98, 52 - 50);
+        assert_eq!((map.map(79), map.map(98), map.map(10)), (81, 50, 10));
+        let seeds = set(&[79..93, 55..68, 96..100, 100..101]);
+        assert_eq!(
+            map.map_set(&seeds).ranges(),
+            &[50..52, 57..70, 81..95, 98..101]
+        );
+    }
+}
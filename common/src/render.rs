@@ -0,0 +1,422 @@
+// Pictures of simulations, for watching them at work.
+//
+// A simulation implements `Render` to draw its current state into a `Frame`,
+// a grid of characters with a colour each. A `Recorder` writes the frames it
+// is given to disk in the format told by the extension of its path:
+//  - `.txt`: all frames as text, separated by blank lines,
+//  - `.ppm`: one PPM image per frame, numbered (`out.ppm` -> `out-00001.ppm`),
+//  - `.gif`: a single animated GIF.
+//
+// Days take the output path as the second command line argument, after the
+// input (see `Recorder::from_args`).
+
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::grid::{Grid, Pos};
+
+pub type Rgb = [u8; 3];
+
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Rgb,
+}
+
+impl Cell {
+    pub fn new(glyph: char, color: Rgb) -> Self {
+        Self { glyph, color }
+    }
+}
+
+// Characters get the colour they most often stand for in the puzzles: empty
+// space is dark, walls and rocks are white, digits are shades of green
+// (brighter is higher).
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        let color = match glyph {
+            '.' | ' ' => [0x0f, 0x0f, 0x23],
+            '#' => [0xff, 0xff, 0xff],
+            'o' => [0xe6, 0xc2, 0x6b],
+            '@' => [0xff, 0x8c, 0x00],
+            'H' | 'S' | 'E' | '^' => [0xe0, 0x30, 0x30],
+            '0'..='9' => {
+                let level = glyph as u8 - b'0';
+                [0x10 + level * 8, 0x40 + level * 20, 0x10 + level * 8]
+            }
+            _ => [0xc0, 0xc0, 0xc0],
+        };
+        Self { glyph, color }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    // A frame of empty space.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::new(width, height, Cell::from('.')),
+        }
+    }
+
+    pub fn from_grid<T, F: FnMut(&T) -> Cell>(grid: &Grid<T>, cell: F) -> Self {
+        Self {
+            cells: grid.map(cell),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<Cell> {
+        self.cells.get(pos).copied()
+    }
+
+    // Returns false (and draws nothing) outside of the frame.
+    pub fn set<C: Into<Cell>>(&mut self, pos: Pos, cell: C) -> bool {
+        self.cells.set(pos, cell.into())
+    }
+
+    // Like `set`, for coordinates that may be negative.
+    pub fn draw<C: Into<Cell>>(&mut self, x: i64, y: i64, cell: C) -> bool {
+        match self.cells.pos(x, y) {
+            Some(pos) => self.set(pos, cell),
+            None => false,
+        }
+    }
+
+    // The colours of the frame, every cell as a square of `scale` pixels,
+    // row by row.
+    pub fn pixels(&self, scale: usize) -> Vec<Rgb> {
+        let mut pixels = Vec::with_capacity(self.width() * self.height() * scale * scale);
+        for row in self.cells.rows() {
+            for _ in 0..scale {
+                for cell in row {
+                    pixels.extend(std::iter::repeat_n(cell.color, scale));
+                }
+            }
+        }
+        pixels
+    }
+
+    // The frame as a binary PPM (P6) image.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let mut ppm = format!(
+            "P6\n{} {}\n255\n",
+            self.width() * scale,
+            self.height() * scale
+        )
+        .into_bytes();
+        ppm.extend(self.pixels(scale).into_iter().flatten());
+        ppm
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.cells.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.glyph)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ascii,
+    Ppm,
+    Gif,
+}
+
+impl Format {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension().and_then(OsStr::to_str)? {
+            "txt" => Some(Format::Ascii),
+            "ppm" => Some(Format::Ppm),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+enum Output {
+    None,
+    Ascii(BufWriter<File>),
+    Ppm,
+    Gif(Option<gif::Encoder<BufWriter<File>>>),
+}
+
+pub struct Recorder {
+    path: PathBuf,
+    output: Output,
+    scale: usize,
+    every: usize,
+    delay: u16,
+    offered: usize,
+    written: usize,
+}
+
+impl Recorder {
+    // A recorder that drops all frames, for running without recording.
+    pub fn none() -> Self {
+        Self {
+            path: PathBuf::new(),
+            output: Output::None,
+            scale: 1,
+            every: 1,
+            delay: 5,
+            offered: 0,
+            written: 0,
+        }
+    }
+
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let output = match Format::from_path(path) {
+            Some(Format::Ascii) => Output::Ascii(BufWriter::new(File::create(path)?)),
+            Some(Format::Ppm) => Output::Ppm,
+            // The size of the GIF is known with the first frame.
+            Some(Format::Gif) => Output::Gif(None),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{}: expected a .txt, .ppm or .gif file", path.display()),
+                ))
+            }
+        };
+        let scale = if output_is_image(&output) { 4 } else { 1 };
+        Ok(Self {
+            path: path.to_path_buf(),
+            output,
+            scale,
+            ..Self::none()
+        })
+    }
+
+    // Records to the path given as the second command line argument, if any.
+    pub fn from_args() -> io::Result<Self> {
+        match env::args().nth(2) {
+            Some(path) => Self::create(path),
+            None => Ok(Self::none()),
+        }
+    }
+
+    // Size of a cell in pixels, for images.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    // Keeps only every `n`th frame, for long simulations.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    // Time between the frames of a GIF, in hundredths of a second.
+    pub fn delay(mut self, delay: u16) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn is_recording(&self) -> bool {
+        !matches!(self.output, Output::None)
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.written
+    }
+
+    // Renders `scene` unless the frame is skipped or nothing is recorded.
+    pub fn record<S: Render + ?Sized>(&mut self, scene: &S) -> io::Result<()> {
+        if !self.is_recording() {
+            return Ok(());
+        }
+        self.offered += 1;
+        if !(self.offered - 1).is_multiple_of(self.every) {
+            return Ok(());
+        }
+        self.write(&scene.render())
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        match &mut self.output {
+            Output::None => return Ok(()),
+            Output::Ascii(out) => {
+                if self.written > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "{}", frame)?;
+            }
+            Output::Ppm => {
+                let stem = self
+                    .path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or("frame");
+                let path =
+                    self.path
+                        .with_file_name(format!("{}-{:05}.ppm", stem, self.written + 1));
+                File::create(path)?.write_all(&frame.to_ppm(self.scale))?;
+            }
+            Output::Gif(slot) => {
+                let (width, height) = gif_size(frame, self.scale)?;
+                let encoder = match slot {
+                    Some(encoder) => encoder,
+                    None => {
+                        let file = BufWriter::new(File::create(&self.path)?);
+                        let mut encoder = gif::Encoder::new(file, width, height, &[])
+                            .map_err(io::Error::other)?;
+                        encoder
+                            .set_repeat(gif::Repeat::Infinite)
+                            .map_err(io::Error::other)?;
+                        slot.insert(encoder)
+                    }
+                };
+                let mut gif_frame = gif_frame(frame, self.scale, width, height)?;
+                gif_frame.delay = self.delay;
+                encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    // Flushes the output and tells the number of frames written.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.output {
+            Output::Ascii(mut out) => out.flush()?,
+            Output::Gif(Some(encoder)) => encoder.into_inner()?.flush()?,
+            Output::None | Output::Ppm | Output::Gif(None) => {}
+        }
+        Ok(self.written)
+    }
+}
+
+fn output_is_image(output: &Output) -> bool {
+    matches!(output, Output::Ppm | Output::Gif(_))
+}
+
+fn gif_size(frame: &Frame, scale: usize) -> io::Result<(u16, u16)> {
+    let size = |cells: usize| {
+        u16::try_from(cells * scale)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large for a GIF"))
+    };
+    Ok((size(frame.width())?, size(frame.height())?))
+}
+
+// A GIF frame of the size of the first one: larger frames are cut, smaller
+// ones are shown in the top-left corner.
+fn gif_frame(
+    frame: &Frame,
+    scale: usize,
+    width: u16,
+    height: u16,
+) -> io::Result<gif::Frame<'static>> {
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut index = |color: Rgb| -> io::Result<u8> {
+        let i = match palette.iter().position(|&c| c == color) {
+            Some(i) => i,
+            None => {
+                palette.push(color);
+                palette.len() - 1
+            }
+        };
+        u8::try_from(i).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "more than 256 colours in a frame",
+            )
+        })
+    };
+    let background = index(Cell::from('.').color)?;
+    let (frame_width, frame_height) = (frame.width() * scale, frame.height() * scale);
+    let pixels = frame.pixels(scale);
+    let mut indexes = vec![background; width as usize * height as usize];
+    for y in 0..frame_height.min(height as usize) {
+        for x in 0..frame_width.min(width as usize) {
+            indexes[y * width as usize + x] = index(pixels[y * frame_width + x])?;
+        }
+    }
+    let palette: Vec<u8> = palette.into_iter().flatten().collect();
+    Ok(gif::Frame::from_palette_pixels(
+        width, height, indexes, palette, None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    struct Counter(usize);
+
+    impl Render for Counter {
+        fn render(&self) -> Frame {
+            let mut frame = Frame::new(3, 2);
+            frame.set((self.0 % 3, 1), '#');
+            frame
+        }
+    }
+
+    #[test]
+    fn frames_render_as_text_and_ppm() {
+        let frame = Counter(1).render();
+        assert_eq!(frame.to_string(), "...\n.#.");
+        let ppm = frame.to_ppm(2);
+        assert!(ppm.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 6 * 4 * 3);
+        assert_eq!(frame.pixels(1)[4], [0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn recorders_write_every_format() {
+        let dir = env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["frames.txt", "frames.ppm", "frames.gif"] {
+            let mut recorder = Recorder::create(dir.join(name)).unwrap().every(2);
+            for i in 0..5 {
+                recorder.record(&Counter(i)).unwrap();
+            }
+            assert_eq!(recorder.finish().unwrap(), 3, "{}", name);
+        }
+        assert_eq!(
+            fs::read_to_string(dir.join("frames.txt")).unwrap(),
+            "...\n#..\n\n...\n..#\n\n...\n.#.\n"
+        );
+        assert!(dir.join("frames-00003.ppm").is_file());
+        let mut gif = gif::DecodeOptions::new()
+            .read_info(File::open(dir.join("frames.gif")).unwrap())
+            .unwrap();
+        assert_eq!((gif.width(), gif.height()), (12, 8));
+        let mut frames = 0;
+        while gif.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+        assert!(Recorder::create(dir.join("frames.png")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,404 @@
+// Graph searches over implicit graphs: breadth-first, Dijkstra and A*.
+//
+// A search starts from one or more states and expands them with a successor
+// function until a goal state is reached (or everything reachable has been
+// seen, if nothing is a goal). States are deduplicated by a key, which is the
+// state itself for the plain variants and can be anything hashable for the
+// `_by_key` ones, e.g. to ignore parts of a state that don't matter.
+//
+// The result keeps the cost of and the predecessor of every reached state,
+// so that paths can be reconstructed afterwards.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Anything usable as the cost of a step; `Default` is the zero cost.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+#[derive(Debug, Clone)]
+struct Node<S, K, C> {
+    state: S,
+    cost: C,
+    parent: Option<K>,
+    closed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Explored<S, K, C> {
+    nodes: HashMap<K, Node<S, K, C>>,
+    goal: Option<K>,
+}
+
+impl<S, K: Hash + Eq, C: Copy> Explored<S, K, C> {
+    fn new() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    // The goal state the search stopped at, if any was reached.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref().map(|key| &self.nodes[key].state)
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().map(|key| self.nodes[key].cost)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<&S>> {
+        self.goal.as_ref().and_then(|key| self.path(key))
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    // Number of states reached.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Lowest known cost of reaching the state. States that were reached but
+    // not expanded yet (when the search stopped at a goal) may still have a
+    // cheaper path.
+    pub fn cost(&self, key: &K) -> Option<C> {
+        self.nodes.get(key).map(|node| node.cost)
+    }
+
+    pub fn predecessor(&self, key: &K) -> Option<&S> {
+        let parent = self.nodes.get(key)?.parent.as_ref()?;
+        Some(&self.nodes[parent].state)
+    }
+
+    // All the states on the path from a start to the state, both included.
+    pub fn path(&self, key: &K) -> Option<Vec<&S>> {
+        let mut node = self.nodes.get(key)?;
+        let mut path = vec![&node.state];
+        while let Some(parent) = &node.parent {
+            node = &self.nodes[parent];
+            path.push(&node.state);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn costs(&self) -> impl Iterator<Item = (&K, C)> {
+        self.nodes.iter().map(|(key, node)| (key, node.cost))
+    }
+
+    pub fn states(&self) -> impl Iterator<Item = (&S, C)> {
+        self.nodes.values().map(|node| (&node.state, node.cost))
+    }
+}
+
+pub fn bfs<S, I, FN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    successors: FN,
+    is_goal: FG,
+) -> Explored<S, S, usize>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    bfs_by_key(starts, S::clone, successors, is_goal)
+}
+
+pub fn bfs_by_key<S, K, I, FK, FN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut key: FK,
+    mut successors: FN,
+    mut is_goal: FG,
+) -> Explored<S, K, usize>
+where
+    S: Clone,
+    K: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+    FK: FnMut(&S) -> K,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    let mut explored = Explored::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = explored.nodes.entry(key(&start)) {
+            queue.push_back(entry.key().clone());
+            entry.insert(Node {
+                state: start,
+                cost: 0,
+                parent: None,
+                closed: true,
+            });
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let node = &explored.nodes[&current];
+        let (state, cost) = (node.state.clone(), node.cost);
+        if is_goal(&state) {
+            explored.goal = Some(current);
+            break;
+        }
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = explored.nodes.entry(key(&next)) {
+                queue.push_back(entry.key().clone());
+                entry.insert(Node {
+                    state: next,
+                    cost: cost + 1,
+                    parent: Some(current.clone()),
+                    closed: true,
+                });
+            }
+        }
+    }
+
+    explored
+}
+
+pub fn dijkstra<S, C, I, FN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    successors: FN,
+    is_goal: FG,
+) -> Explored<S, S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    astar_by_key(starts, S::clone, successors, |_| C::default(), is_goal)
+}
+
+pub fn dijkstra_by_key<S, K, C, I, FK, FN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    key: FK,
+    successors: FN,
+    is_goal: FG,
+) -> Explored<S, K, C>
+where
+    S: Clone,
+    K: Clone + Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    FK: FnMut(&S) -> K,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    astar_by_key(starts, key, successors, |_| C::default(), is_goal)
+}
+
+// The heuristic must never overestimate the remaining cost to a goal, and
+// must be consistent (it may not drop by more than the cost of a step), for
+// the first goal reached to be the cheapest one.
+pub fn astar<S, C, I, FN, FH, FG>(
+    starts: impl IntoIterator<Item = S>,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FG,
+) -> Explored<S, S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    FN: FnMut(&S) -> I,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    astar_by_key(starts, S::clone, successors, heuristic, is_goal)
+}
+
+pub fn astar_by_key<S, K, C, I, FK, FN, FH, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut key: FK,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Explored<S, K, C>
+where
+    S: Clone,
+    K: Clone + Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    FK: FnMut(&S) -> K,
+    FN: FnMut(&S) -> I,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut explored: Explored<S, K, C> = Explored::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        let priority = heuristic(&start);
+        if let Entry::Vacant(entry) = explored.nodes.entry(key(&start)) {
+            queue.push(Queued {
+                priority,
+                cost: C::default(),
+                key: entry.key().clone(),
+            });
+            entry.insert(Node {
+                state: start,
+                cost: C::default(),
+                parent: None,
+                closed: false,
+            });
+        }
+    }
+
+    while let Some(Queued {
+        cost, key: current, ..
+    }) = queue.pop()
+    {
+        let node = explored.nodes.get_mut(&current).unwrap();
+        // a cheaper path to the state has been queued (and maybe expanded)
+        // after this entry
+        if node.closed || cost > node.cost {
+            continue;
+        }
+        node.closed = true;
+        let state = node.state.clone();
+        if is_goal(&state) {
+            explored.goal = Some(current);
+            break;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let next_node = Node {
+                state: next,
+                cost: next_cost,
+                parent: Some(current.clone()),
+                closed: false,
+            };
+            let next_key = match explored.nodes.entry(key(&next_node.state)) {
+                Entry::Occupied(mut entry) => {
+                    if entry.get().closed || entry.get().cost <= next_cost {
+                        continue;
+                    }
+                    entry.insert(next_node);
+                    entry.key().clone()
+                }
+                Entry::Vacant(entry) => {
+                    let next_key = entry.key().clone();
+                    entry.insert(next_node);
+                    next_key
+                }
+            };
+            let priority = next_cost + heuristic(&explored.nodes[&next_key].state);
+            queue.push(Queued {
+                priority,
+                cost: next_cost,
+                key: next_key,
+            });
+        }
+    }
+
+    explored
+}
+
+// Entry of the priority queue, popping the lowest priority first.
+struct Queued<K, C> {
+    priority: C,
+    cost: C,
+    key: K,
+}
+
+impl<K, C: Ord> Ord for Queued<K, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<K, C: Ord> PartialOrd for Queued<K, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, C: Ord> PartialEq for Queued<K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<K, C: Ord> Eq for Queued<K, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#E..
+.####...
+........";
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let explored = bfs(
+            [start],
+            |&pos| grid.neighbours4(pos).filter(|&next| grid[next] != '#'),
+            |&pos| grid[pos] == 'E',
+        );
+        assert_eq!(explored.goal_cost(), Some(11));
+        let path = explored.goal_path().unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!(*path[0], start);
+        assert_eq!(grid[*path[11]], 'E');
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        // moving down is cheap, everything else is expensive
+        let successors = |&(x, y): &(i64, i64)| {
+            [((x + 1, y), 5), ((x, y + 1), 1), ((x - 1, y), 5)]
+                .into_iter()
+                .filter(|&((x, y), _)| (0..10).contains(&x) && (0..10).contains(&y))
+        };
+        let goal = |&p: &(i64, i64)| p == (9, 9);
+        let explored = dijkstra([(0, 0)], successors, goal);
+        assert_eq!(explored.goal_cost(), Some(9 * 5 + 9));
+        let explored = astar([(0, 0)], successors, |&(x, y)| (9 - x) * 5 + (9 - y), goal);
+        assert_eq!(explored.goal_cost(), Some(9 * 5 + 9));
+        assert_eq!(explored.predecessor(&(0, 0)), None);
+        assert_eq!(explored.goal_path().unwrap().len(), 19);
+    }
+
+    #[test]
+    fn whole_graph_by_key() {
+        // states carry a step counter that is ignored for deduplication
+        let explored = dijkstra_by_key(
+            [(0u32, 0u32)],
+            |&(node, _)| node,
+            |&(node, steps)| {
+                [(node + 1) % 6, (node + 5) % 6].map(|next| ((next, steps + 1), next.max(node)))
+            },
+            |_| false,
+        );
+        assert_eq!(explored.goal(), None);
+        assert_eq!(explored.len(), 6);
+        assert_eq!(explored.cost(&3), Some(6));
+        assert_eq!(explored.cost(&5), Some(5));
+        assert_eq!(explored.path(&5).unwrap().len(), 2);
+        assert_eq!(explored.path(&3).unwrap().len(), 4);
+    }
+}
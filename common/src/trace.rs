@@ -0,0 +1,38 @@
+// Diagnostics of the solvers. Solvers log with the `tracing` macros
+// re-exported here and stay quiet unless a filter enables their logs at run
+// time, from `AOC_LOG` or from the command line of `aoc`:
+//
+//     AOC_LOG=debug                      everything from debug level on
+//     AOC_LOG=aoc2022_day19=trace        one day in detail, by package name
+//     AOC_LOG=warn,aoc2022_day17=debug
+//
+// Logs go to the standard error, leaving the standard output to answers.
+
+use std::env;
+use std::io::{self, IsTerminal};
+use tracing_subscriber::EnvFilter;
+
+pub use tracing::{debug, error, info, trace, warn};
+
+pub const LOG_VAR: &str = "AOC_LOG";
+
+// Only warnings are shown when nothing is selected.
+const DEFAULT_FILTER: &str = "warn";
+
+// Installs the logger with `filter`, or with the filter of `AOC_LOG` when
+// there is none.
+pub fn init(filter: Option<&str>) -> Result<(), String> {
+    let filter = match filter {
+        Some(filter) => filter.to_string(),
+        None => env::var(LOG_VAR).unwrap_or_else(|_| DEFAULT_FILTER.to_string()),
+    };
+    let env_filter = EnvFilter::try_new(&filter)
+        .map_err(|e| format!("invalid log filter '{}': {}", filter, e))?;
+    tracing_subscriber::fmt()
+        .with_env_filter(env_filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .try_init()
+        .map_err(|e| e.to_string())
+}